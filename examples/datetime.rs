@@ -29,5 +29,12 @@ fn main() {
         .interact()
         .unwrap();
     println!("Datetime selected {}", time);
+
+    let (start, end) = DateTimeSelect::new()
+        .with_prompt("Pick a date range")
+        .date_type(DateType::Date)
+        .interact_range()
+        .unwrap();
+    println!("Range selected {} to {}", start.date(), end.date());
 }
 
@@ -1,9 +1,21 @@
+use std::collections::HashMap;
+use std::env;
+use std::error;
+use std::fmt;
+use std::fmt::Write;
 use std::io;
+use std::rc::Rc;
+use std::time::{Duration as StdDuration, Instant};
 
-use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Timelike, Utc};
-use console::{style, Key, Term};
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, Offset, ParseError,
+    SecondsFormat, TimeZone, Timelike, Utc, Weekday,
+};
+#[cfg(feature = "chrono-tz")]
+use chrono_tz::Tz;
+use console::{measure_text_width, style, truncate_str, Key, Term, TermTarget};
 use std::cmp::{max, min};
-use theme::{get_default_theme, TermThemeRenderer, Theme};
+use theme::{get_default_theme, Theme};
 
 trait DateAdjust {
     fn increment_year(&self) -> Self;
@@ -12,96 +24,860 @@ trait DateAdjust {
     fn decrement_month(&self) -> Self;
 }
 
-static MONTH_END_DAYS: &[u32] = &[0, 31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
-
 impl<T> DateAdjust for T
 where
-    T: Datelike,
+    T: Datelike + Clone,
 {
     fn increment_year(&self) -> Self {
-        self.with_year(self.year() + 1).unwrap_or_else(|| {
-            // If we're currently on a leap day we know how to handle a failure
-            assert_eq!(self.month(), 2, "Unexpected failure in year increment. Please open a bug ticket with the current case.");
-            assert_eq!(self.day(), 29, "Unexpected failure in year increment. Please open a bug ticket with the current case.");
-
-            self.with_day(28).unwrap().with_year(self.year() + 1).unwrap()
-        })
+        let year = self.year() + 1;
+        self.with_year(year)
+            // If we're currently on a leap day step from the 28th instead.
+            .or_else(|| self.with_day(28).and_then(|val| val.with_year(year)))
+            .unwrap_or_else(|| unchanged(self, "year increment"))
     }
 
     fn decrement_year(&self) -> Self {
-        self.with_year(self.year() - 1).unwrap_or_else(|| {
-            // If we're currently on a leap day we know how to handle a failure
-            assert_eq!(self.month(), 2, "Unexpected failure in year decrement. Please open a bug ticket with the current case.");
-            assert_eq!(self.day(), 29, "Unexpected failure in year decrement. Please open a bug ticket with the current case.");
-
-            self.with_day(28).unwrap().with_year(self.year() - 1).unwrap()
-        })
+        let year = self.year() - 1;
+        self.with_year(year)
+            // If we're currently on a leap day step from the 28th instead.
+            .or_else(|| self.with_day(28).and_then(|val| val.with_year(year)))
+            .unwrap_or_else(|| unchanged(self, "year decrement"))
     }
 
     fn increment_month(&self) -> Self {
-        let new_month = self.month() + 1;
-        if new_month > 12 {
-            // This case should be infallible since both December and January have 31 days
-            self.with_year(self.year() + 1).unwrap().with_month(1).unwrap()
-        } else {
-            self.with_month(new_month).unwrap_or_else(|| {
-                // We've stepped off the end of the month most likely, adjust the day if so
-                assert!(
-                    self.day() > MONTH_END_DAYS[new_month as usize],
-                    "Unexpected failure in month increment. Please open a bug ticket with the current case."
-                );
+        let (year, month) = if self.month() == 12 { (self.year() + 1, 1) } else { (self.year(), self.month() + 1) };
+        // Clamp the day to the end of the new month in case we step off of it.
+        let day = min(self.day(), days_in_month(year, month));
+        self.with_day(day)
+            .and_then(|val| val.with_year(year))
+            .and_then(|val| val.with_month(month))
+            .unwrap_or_else(|| unchanged(self, "month increment"))
+    }
+
+    fn decrement_month(&self) -> Self {
+        let (year, month) = if self.month() == 1 { (self.year() - 1, 12) } else { (self.year(), self.month() - 1) };
+        // Clamp the day to the end of the new month in case we step off of it.
+        let day = min(self.day(), days_in_month(year, month));
+        self.with_day(day)
+            .and_then(|val| val.with_year(year))
+            .and_then(|val| val.with_month(month))
+            .unwrap_or_else(|| unchanged(self, "month decrement"))
+    }
+}
+
+/// Keeps a date unchanged when an adjustment fails, which only happens at the limits of the calendar.
+///
+/// Debug builds still fail loudly so such a case gets noticed, release builds just ignore the step.
+fn unchanged<T: Clone>(val: &T, adjustment: &str) -> T {
+    debug_assert!(
+        false,
+        "Unexpected failure in {}. Please open a bug ticket with the current case.",
+        adjustment
+    );
+    val.clone()
+}
+
+/// Month and weekday names for a language, weekdays start on Monday.
+struct Names {
+    months: [&'static str; 12],
+    short_months: [&'static str; 12],
+    weekdays: [&'static str; 7],
+    short_weekdays: [&'static str; 7],
+}
+
+static EN_NAMES: Names = Names {
+    months: [
+        "January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November", "December",
+    ],
+    short_months: ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"],
+    weekdays: ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"],
+    short_weekdays: ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+};
+
+static DE_NAMES: Names = Names {
+    months: [
+        "Januar", "Februar", "März", "April", "Mai", "Juni", "Juli", "August", "September", "Oktober", "November", "Dezember",
+    ],
+    short_months: ["Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez"],
+    weekdays: ["Montag", "Dienstag", "Mittwoch", "Donnerstag", "Freitag", "Samstag", "Sonntag"],
+    short_weekdays: ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"],
+};
+
+static ES_NAMES: Names = Names {
+    months: [
+        "enero", "febrero", "marzo", "abril", "mayo", "junio", "julio", "agosto", "septiembre", "octubre", "noviembre", "diciembre",
+    ],
+    short_months: ["ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sep", "oct", "nov", "dic"],
+    weekdays: ["lunes", "martes", "miércoles", "jueves", "viernes", "sábado", "domingo"],
+    short_weekdays: ["lun", "mar", "mié", "jue", "vie", "sáb", "dom"],
+};
+
+static FR_NAMES: Names = Names {
+    months: [
+        "janvier", "février", "mars", "avril", "mai", "juin", "juillet", "août", "septembre", "octobre", "novembre", "décembre",
+    ],
+    short_months: ["janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.", "nov.", "déc."],
+    weekdays: ["lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche"],
+    short_weekdays: ["lun.", "mar.", "mer.", "jeu.", "ven.", "sam.", "dim."],
+};
+
+static IT_NAMES: Names = Names {
+    months: [
+        "gennaio", "febbraio", "marzo", "aprile", "maggio", "giugno", "luglio", "agosto", "settembre", "ottobre", "novembre", "dicembre",
+    ],
+    short_months: ["gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic"],
+    weekdays: ["lunedì", "martedì", "mercoledì", "giovedì", "venerdì", "sabato", "domenica"],
+    short_weekdays: ["lun", "mar", "mer", "gio", "ven", "sab", "dom"],
+};
+
+static NL_NAMES: Names = Names {
+    months: [
+        "januari", "februari", "maart", "april", "mei", "juni", "juli", "augustus", "september", "oktober", "november", "december",
+    ],
+    short_months: ["jan", "feb", "mrt", "apr", "mei", "jun", "jul", "aug", "sep", "okt", "nov", "dec"],
+    weekdays: ["maandag", "dinsdag", "woensdag", "donderdag", "vrijdag", "zaterdag", "zondag"],
+    short_weekdays: ["ma", "di", "wo", "do", "vr", "za", "zo"],
+};
+
+static PT_NAMES: Names = Names {
+    months: [
+        "janeiro", "fevereiro", "março", "abril", "maio", "junho", "julho", "agosto", "setembro", "outubro", "novembro", "dezembro",
+    ],
+    short_months: ["jan", "fev", "mar", "abr", "mai", "jun", "jul", "ago", "set", "out", "nov", "dez"],
+    weekdays: ["segunda-feira", "terça-feira", "quarta-feira", "quinta-feira", "sexta-feira", "sábado", "domingo"],
+    short_weekdays: ["seg", "ter", "qua", "qui", "sex", "sáb", "dom"],
+};
+
+/// Looks up the names for a locale like "de", "de_DE" or "de-DE.UTF-8", falling back to English.
+fn names_for_locale(locale: &str) -> &'static Names {
+    let language = locale.split(&['_', '-', '.'][..]).next().unwrap_or("").to_lowercase();
+    match language.as_str() {
+        "de" => &DE_NAMES,
+        "es" => &ES_NAMES,
+        "fr" => &FR_NAMES,
+        "it" => &IT_NAMES,
+        "nl" => &NL_NAMES,
+        "pt" => &PT_NAMES,
+        _ => &EN_NAMES,
+    }
+}
+
+static HELP_TEXT: &str = "←/→ move · ↑/↓ change · 0-9 type · Enter confirm · ? hide help";
+
+/// The named timezones cycled through by the timezone field.
+#[cfg(feature = "chrono-tz")]
+static TIMEZONES: &[Tz] = &[
+    Tz::UTC,
+    Tz::Pacific__Honolulu,
+    Tz::America__Anchorage,
+    Tz::America__Los_Angeles,
+    Tz::America__Denver,
+    Tz::America__Chicago,
+    Tz::America__New_York,
+    Tz::America__Sao_Paulo,
+    Tz::Europe__London,
+    Tz::Europe__Paris,
+    Tz::Europe__Berlin,
+    Tz::Europe__Athens,
+    Tz::Europe__Moscow,
+    Tz::Africa__Johannesburg,
+    Tz::Asia__Dubai,
+    Tz::Asia__Kolkata,
+    Tz::Asia__Shanghai,
+    Tz::Asia__Tokyo,
+    Tz::Australia__Sydney,
+    Tz::Pacific__Auckland,
+];
+
+/// Returns the UTC offset of a wall clock time in the given timezone.
+///
+/// Ambiguous times pick the earlier offset, times skipped by DST use the offset after it.
+fn offset_at<Z: TimeZone>(zone: &Z, val: &NaiveDateTime) -> FixedOffset {
+    zone.from_local_datetime(val)
+        .earliest()
+        .map(|dt| dt.offset().fix())
+        .unwrap_or_else(|| zone.offset_from_utc_datetime(val).fix())
+}
+
+/// The most dates skipped over in one step before giving up on finding an enabled date.
+const MAX_SKIPPED: usize = 1000;
 
-                self.with_day(MONTH_END_DAYS[new_month as usize]).unwrap().with_month(new_month).unwrap()
-            })
+/// Steps arriving closer together than this count as a held key.
+const REPEAT_INTERVAL_MS: u64 = 100;
+
+/// How many quick steps in a row are made before stepping faster.
+const ACCELERATE_AFTER: usize = 10;
+
+/// How many steps a single held key press makes once accelerated.
+const ACCELERATED_STEPS: usize = 10;
+
+/// A validator run on Enter, returning the error to show if the value is rejected.
+type DateTimeValidator = dyn Fn(&NaiveDateTime) -> Option<String>;
+
+/// A callback getting the value after every edit.
+type ChangeCallback = dyn Fn(&NaiveDateTime);
+
+/// The state of a selection while keys are applied to it.
+///
+/// Created by `DateTimeSelect::state` to drive a selection one key at a time with
+/// `DateTimeSelect::apply_key`, e.g. inside a larger terminal UI that does its own rendering.
+pub struct DateTimeSelectState {
+    date_val: NaiveDateTime,
+    /// The endpoint of a range that is not being edited.
+    other_val: NaiveDateTime,
+    /// Whether `date_val` is the end of a range rather than its start.
+    editing_end: bool,
+    range: bool,
+    allow_quit: bool,
+    fields: Vec<DateField>,
+    pos: usize,
+    digits: Vec<u32>,
+    show_help: bool,
+    month_name: String,
+    zone: Zone,
+    /// An error shown below the datetime until the next key press.
+    feedback: Option<String>,
+    /// Whether the user moved or edited anything yet.
+    touched: bool,
+    /// Whether no value was entered yet with `allow_empty`.
+    empty: bool,
+}
+
+impl DateTimeSelectState {
+    /// Returns the value currently being edited.
+    pub fn current(&self) -> NaiveDateTime {
+        self.date_val
+    }
+    /// Returns the index of the field being edited within `fields`.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+    /// Returns the fields that can be edited, in display order.
+    pub fn fields(&self) -> &[DateField] {
+        &self.fields
+    }
+    /// Returns whether no value was entered yet, see `DateTimeSelect::allow_empty`.
+    pub fn is_empty(&self) -> bool {
+        self.empty
+    }
+    /// Returns the error to show below the datetime, e.g. why a confirmation was rejected.
+    pub fn feedback(&self) -> Option<&str> {
+        self.feedback.as_deref()
+    }
+
+    /// Returns the value to confirm, ordering the endpoints of a range.
+    fn selected(&self) -> Selected {
+        let (start, end) = if self.editing_end { (self.other_val, self.date_val) } else { (self.date_val, self.other_val) };
+        Selected {
+            start,
+            end: if self.range { end } else { start },
+            zone: self.zone,
+            empty: self.empty,
         }
     }
+}
 
-    fn decrement_month(&self) -> Self {
-        let new_month = self.month() - 1;
-        if new_month < 1 {
-            // This case should be infallible since both December and January have 31 days
-            self.with_year(self.year() - 1).unwrap().with_month(12).unwrap()
+/// What happened after a key was applied to a selection.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KeyOutcome {
+    /// The selection goes on.
+    Continue,
+    /// The key was rejected, the terminal bell should be rung before going on.
+    ///
+    /// `DateTimeSelectState::feedback` holds the reason to show, if there is one.
+    Bell,
+    /// The user confirmed the current value.
+    Confirm,
+    /// The user cancelled the selection.
+    Cancel,
+}
+
+/// The components of a selected datetime, see `DateTimeSelect::interact_parts`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DateTimeParts {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    pub nanosecond: u32,
+    /// Whether the seconds could be edited, they are zero otherwise.
+    pub seconds_shown: bool,
+    /// The date type the value was selected with, telling which of the components were editable.
+    pub date_type: DateType,
+}
+
+/// The value confirmed by the user, `end` equals `start` unless a range was selected.
+struct Selected {
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+    zone: Zone,
+    /// Whether the selection was confirmed without a value, see `allow_empty`.
+    empty: bool,
+}
+
+/// Returns whether the terminal should get unstyled output, as it is not a tty or `NO_COLOR` is set.
+fn plain_output(term: &Term) -> bool {
+    let no_color = match env::var_os("NO_COLOR") {
+        Some(val) => !val.is_empty(),
+        None => false,
+    };
+    no_color || !term.is_term()
+}
+
+/// Writes a single field with the theme's styling and the active marker.
+///
+/// Plain output has no styling, so the active field gets brackets unless a caret marks it.
+fn write_field(theme: &dyn Theme, f: &mut String, value: &str, active: bool, plain: bool, marker: Marker) {
+    let mut styled = String::new();
+    // Writing to a String can not fail.
+    let _ = if plain {
+        write!(styled, "{}", value)
+    } else {
+        theme.format_datetime_field(&mut styled, value, active)
+    };
+    let _ = match (marker, plain, active) {
+        (_, _, false) | (Marker::Caret, _, _) | (Marker::Style, false, _) => write!(f, "{}", styled),
+        (Marker::Underline, false, _) => write!(f, "{}", style(styled).underlined()),
+        _ => write!(f, "[{}]", styled),
+    };
+}
+
+/// Returns how many terminal rows a line of text takes up once it wraps.
+///
+/// A width of 0 is guarded against rather than divided by, and counts as no wrapping. Off a
+/// terminal `Term::size` reports 79 columns, so lines are counted as wrapping at that width.
+fn wrapped_rows(text_width: usize, term_width: usize) -> usize {
+    match term_width {
+        0 => 1,
+        _ => text_width.saturating_sub(1) / term_width + 1,
+    }
+}
+
+/// Shortens a prompt with an ellipsis so it and a value of `value_width` fit on one line of `term_width`.
+///
+/// `decoration` is the width the theme adds around the prompt. If not even the start of the prompt
+/// fits it is kept as is, the frame then tracks the rows it wraps onto.
+fn fit_prompt(prompt: &str, decoration: usize, value_width: usize, term_width: usize) -> String {
+    let available = term_width.saturating_sub(decoration + value_width);
+    if measure_text_width(prompt) <= available || available < 2 {
+        return prompt.to_string();
+    }
+    format!("{}…", truncate_str(prompt, available - 1, "").trim_end())
+}
+
+/// The lines of a selection on the terminal, only redrawn from the first line that changed.
+#[derive(Default)]
+struct Frame {
+    lines: Vec<String>,
+    /// The terminal rows each line takes up once it wraps.
+    rows: Vec<usize>,
+}
+
+impl Frame {
+    /// Replaces the drawn lines with `lines`, skipping the lines that are unchanged.
+    ///
+    /// The changed lines go out in a single write, as redrawing line by line flickers on slow
+    /// terminals, e.g. over SSH.
+    fn draw(&mut self, term: &Term, lines: Vec<String>) -> io::Result<()> {
+        let unchanged = self.lines.iter().zip(&lines).take_while(|&(drawn, line)| drawn == line).count();
+        if unchanged == lines.len() && unchanged == self.lines.len() {
+            return Ok(());
+        }
+        let stale: usize = self.rows[unchanged..].iter().sum();
+        if stale > 0 {
+            // Move up to the first changed line and clear everything below it.
+            term.move_cursor_up(stale)?;
+            term.clear_to_end_of_screen()?;
+        }
+        let mut buf = String::new();
+        let width = term.size().1 as usize;
+        self.rows.truncate(unchanged);
+        for line in &lines[unchanged..] {
+            buf.push_str(line);
+            buf.push('\n');
+            self.rows.push(wrapped_rows(measure_text_width(line), width));
+        }
+        self.lines = lines;
+        term.write_str(&buf)
+    }
+
+    /// Clears all lines but the first `keep`.
+    fn clear(&mut self, term: &Term, keep: usize) -> io::Result<()> {
+        let keep = min(keep, self.lines.len());
+        term.clear_last_lines(self.rows[keep..].iter().sum())?;
+        self.lines.truncate(keep);
+        self.rows.truncate(keep);
+        Ok(())
+    }
+}
+
+/// Returns the UP/DOWN keys that step a field, which speed up when held with `acceleration`.
+fn repeatable(key: &Key) -> Option<Key> {
+    match *key {
+        Key::ArrowUp => Some(Key::ArrowUp),
+        Key::ArrowDown => Some(Key::ArrowDown),
+        Key::Char(c) if c == 'k' || c == 'j' => Some(Key::Char(c)),
+        _ => None,
+    }
+}
+
+/// Tracks how quickly the same step key repeats to decide how many steps it makes.
+#[derive(Default)]
+struct Acceleration {
+    last: Option<(Key, Instant)>,
+    streak: usize,
+}
+
+impl Acceleration {
+    /// Returns how many times a key read at `now` is applied.
+    fn repeats(&mut self, key: &Key, now: Instant) -> usize {
+        let key = repeatable(key);
+        let quick = match (&key, &self.last) {
+            (Some(key), Some((last, at))) => {
+                key == last && now.duration_since(*at) < StdDuration::from_millis(REPEAT_INTERVAL_MS)
+            }
+            _ => false,
+        };
+        self.streak = if quick { self.streak + 1 } else { 0 };
+        self.last = key.map(|key| (key, now));
+        if self.streak >= ACCELERATE_AFTER {
+            ACCELERATED_STEPS
         } else {
-            self.with_month(new_month).unwrap_or_else(|| {
-                // We've stepped off the end of the month most likely, adjust the day if so
-                assert!(
-                    self.day() > MONTH_END_DAYS[new_month as usize],
-                    "Unexpected failure in month decrement. Please open a bug ticket with the current case."
-                );
+            1
+        }
+    }
+}
+
+/// Reads a key, turning Ctrl-C into an `Interrupted` error also when it arrives as a character.
+fn read_key(term: &Term) -> io::Result<Key> {
+    match term.read_key()? {
+        Key::Char('\u{3}') => Err(io::Error::new(io::ErrorKind::Interrupted, "read interrupted")),
+        key => Ok(key),
+    }
+}
+
+/// Hides the terminal cursor until dropped.
+struct HiddenCursor<'a> {
+    term: &'a Term,
+}
+
+impl<'a> HiddenCursor<'a> {
+    fn hide(term: &'a Term) -> io::Result<HiddenCursor<'a>> {
+        term.hide_cursor()?;
+        Ok(HiddenCursor { term })
+    }
+}
+
+impl<'a> Drop for HiddenCursor<'a> {
+    // Runs on every way out of the selection, so the cursor comes back on errors and Ctrl-C too.
+    fn drop(&mut self) {
+        let _ = self.term.show_cursor();
+    }
+}
+
+/// Returns the number of days in the given month, taking leap years into account.
+///
+/// Panics if the month is not within 1 to 12.
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    NaiveDate::from_ymd(next_year, next_month, 1)
+        .signed_duration_since(NaiveDate::from_ymd(year, month, 1))
+        .num_days() as u32
+}
+
+/// Sets the month, clamping the day to the end of the new month if needed.
+fn with_month_clamped(val: NaiveDateTime, month: u32) -> NaiveDateTime {
+    let day = min(val.day(), days_in_month(val.year(), month));
+    val.with_day(day).and_then(|val| val.with_month(month)).unwrap_or(val)
+}
+
+/// Returns how many digits are typed to set a field.
+fn digits_needed(field: DateField) -> usize {
+    // Need 4 digits to set year, 3 for milliseconds, 9 for nanoseconds, 1 for a weekday and 2 otherwise.
+    match field {
+        DateField::Year | DateField::IsoYear => 4,
+        DateField::Weekday => 1,
+        DateField::Millisecond => 3,
+        DateField::Nanosecond => 9,
+        _ => 2,
+    }
+}
+
+/// Returns the number made up by the digits typed so far.
+fn typed_value(digits: &[u32]) -> u64 {
+    digits.iter().fold(0, |num, &digit| num * 10 + u64::from(digit))
+}
+
+/// Returns whether typed digits are set right away, as `needed` digits were typed or another
+/// digit would go past `highest`.
+fn digits_complete(digits: &[u32], needed: usize, highest: u32) -> bool {
+    digits.len() >= needed || typed_value(digits) * 10 > u64::from(highest)
+}
+
+/// Keeps a time of day between two others, where a `low` after `high` is a window across midnight.
+fn clamp_time(time: NaiveTime, low: NaiveTime, high: NaiveTime) -> NaiveTime {
+    if low <= high {
+        return min(max(time, low), high);
+    }
+    if time >= low || time <= high {
+        return time;
+    }
+    // Outside of the window, snap to whichever end is closer.
+    if time.signed_duration_since(high) < low.signed_duration_since(time) {
+        high
+    } else {
+        low
+    }
+}
+
+/// Returns the number of ISO weeks in an ISO year, 52 or 53.
+fn iso_weeks_in_year(year: i32) -> u32 {
+    // The 28th of December always falls in the last week of its ISO year.
+    NaiveDate::from_ymd(year, 12, 28).iso_week().week()
+}
+
+/// Moves to the given ISO year, week and weekday (1 for Monday), keeping the time of day.
+fn with_iso_week(val: NaiveDateTime, year: i32, week: u32, weekday: u32) -> Option<NaiveDateTime> {
+    if !(1..=7).contains(&weekday) {
+        return None;
+    }
+    NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)
+        .map(|monday| (monday + Duration::days(i64::from(weekday) - 1)).and_time(val.time()))
+}
+
+/// Moves to another ISO year, clamping the week to the weeks of that year.
+fn with_iso_year_clamped(val: NaiveDateTime, year: i32) -> Option<NaiveDateTime> {
+    let week = min(val.iso_week().week(), iso_weeks_in_year(year));
+    with_iso_week(val, year, week, val.weekday().number_from_monday())
+}
+
+/// Errors that can occur when configuring a `DateTimeSelect` or `NumberSelect` with the fallible setters.
+#[derive(Debug)]
+pub enum DateTimeError {
+    /// The provided string did not match the rfc3339 format.
+    Parse(ParseError),
+    /// The maximum is smaller than the minimum, which only a `DateType::Time` allows.
+    ///
+    /// Checked once interacting, so the order of `date_type`, `min` and `max` does not matter.
+    InvalidRange,
+    /// A field range passed to `try_field_bounds` has its low end above its high end.
+    InvalidFieldBounds,
+    /// A step of zero was passed to `try_step`, which would never move a field or number.
+    ZeroStep,
+}
 
-                self.with_day(MONTH_END_DAYS[new_month as usize]).unwrap().with_month(new_month).unwrap()
-            })
+impl fmt::Display for DateTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DateTimeError::Parse(err) => write!(f, "date format must match rfc3339: {}", err),
+            DateTimeError::InvalidRange => write!(f, "maximum must be larger than minimum"),
+            DateTimeError::InvalidFieldBounds => write!(f, "low must not be larger than high"),
+            DateTimeError::ZeroStep => write!(f, "step must be larger than zero"),
         }
     }
 }
 
+impl error::Error for DateTimeError {}
+
+impl From<ParseError> for DateTimeError {
+    fn from(err: ParseError) -> DateTimeError {
+        DateTimeError::Parse(err)
+    }
+}
+
+fn parse_datetime(val: &str) -> Result<NaiveDateTime, ParseError> {
+    Ok(DateTime::parse_from_rfc3339(val)?.naive_local())
+}
+
+/// Parses a min or max, a plain date covers the start or the end of that day.
+fn parse_bound_date(val: &str, end_of_day: bool) -> Result<NaiveDateTime, ParseError> {
+    match NaiveDate::parse_from_str(val, "%Y-%m-%d") {
+        Ok(date) if end_of_day => Ok(date.and_hms(23, 59, 59)),
+        Ok(date) => Ok(date.and_hms(0, 0, 0)),
+        Err(_) => parse_datetime(val),
+    }
+}
+
+/// Parses a string the way `DateTimeSelect::min` does, to validate config up front.
+///
+/// Returns the wall clock time of the string, its offset is dropped. Plain dates like
+/// `2024-01-01` are accepted and give the start of that day, `max` takes the same strings.
+pub fn parse_bound(val: &str) -> Result<NaiveDateTime, DateTimeError> {
+    Ok(parse_bound_date(val, false)?)
+}
+
+/// Returns whether `default` would accept the string without panicking.
+///
+/// Unlike `min` and `max`, `default` needs a full RFC3339 string.
+pub fn is_valid_rfc3339(val: &str) -> bool {
+    parse_datetime(val).is_ok()
+}
+
 /// The possible types of datetime selections that can be made.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum DateType {
     Date,
     Time,
     DateTime,
+    /// Only the year and month, the day is kept on the first of the month.
+    YearMonth,
+    /// An ISO week date of year, week and weekday, like `2024-W10-1`.
+    IsoWeek,
+}
+
+/// The editable fields of a datetime selection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DateField {
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+    /// The milliseconds shown with `Precision::Millis`.
+    Millisecond,
+    /// The nanoseconds shown with `Precision::Nanos`.
+    Nanosecond,
+    /// The AM/PM field shown when `hour12` is enabled.
+    Meridiem,
+    /// The named timezone field shown when `select_timezone` is enabled.
+    Timezone,
+    /// The ISO year of `DateType::IsoWeek`, which can differ from the calendar year around new year.
+    IsoYear,
+    /// The ISO week number of `DateType::IsoWeek`.
+    Week,
+    /// The ISO weekday of `DateType::IsoWeek`, from 1 for Monday to 7 for Sunday.
+    Weekday,
+}
+
+/// The timezone the selected datetime is in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Zone {
+    Utc,
+    /// The local timezone of the machine.
+    Local,
+    /// A timezone with a fixed offset from UTC.
+    Fixed(FixedOffset),
+    /// A named IANA timezone, taking daylight saving time into account.
+    #[cfg(feature = "chrono-tz")]
+    Named(Tz),
+}
+
+/// The clock the current date comes from when no default is set.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DefaultZone {
+    Utc,
+    /// The local timezone of the machine, avoids starting on tomorrow or yesterday west or east of UTC.
+    Local,
+}
+
+impl Zone {
+    /// Returns the next or previous named timezone of the timezone field.
+    #[cfg(feature = "chrono-tz")]
+    fn cycle(self, up: bool) -> Zone {
+        let len = TIMEZONES.len();
+        let idx = match TIMEZONES.iter().position(|&tz| Zone::Named(tz) == self) {
+            Some(idx) if up => (idx + 1) % len,
+            Some(idx) => (idx + len - 1) % len,
+            None if up => 0,
+            None => len - 1,
+        };
+        Zone::Named(TIMEZONES[idx])
+    }
+}
+
+impl fmt::Display for Zone {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Zone::Utc => write!(f, "UTC"),
+            Zone::Local => write!(f, "Local"),
+            Zone::Fixed(offset) => write!(f, "{}", offset),
+            #[cfg(feature = "chrono-tz")]
+            Zone::Named(tz) => write!(f, "{}", tz.name()),
+        }
+    }
+}
+
+/// How the weekday is displayed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WeekdayFormat {
+    /// An abbreviated name like Wed.
+    Short,
+    /// The full name like Wednesday.
+    Long,
+}
+
+/// Where the weekday is displayed relative to the date.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WeekdayPosition {
+    /// Before the date, like `Wed, 2024-03-06`.
+    Prefix,
+    /// After the date, like `2024-03-06, Wed`.
+    Suffix,
+}
+
+/// How precisely the seconds of a Time or DateTime can be edited.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Precision {
+    /// Whole seconds.
+    Seconds,
+    /// An extra field for milliseconds.
+    Millis,
+    /// An extra field for nanoseconds.
+    Nanos,
+}
+
+/// How a value outside of `min`/`max` is brought back into range.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ClampMode {
+    /// Uses the `min` or `max` instant itself.
+    Bound,
+    /// Only changes the edited field, keeping the other fields as they are where possible.
+    SnapToField,
+}
+
+/// How the field being edited is marked, in addition to the theme's styling.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Marker {
+    /// Only the theme's styling, bold by default.
+    Style,
+    /// Underlines the active field.
+    Underline,
+    /// Surrounds the active field with brackets like `[2024]`.
+    Brackets,
+    /// Renders a line of `^` beneath the active field.
+    Caret,
+}
+
+/// What to do when a typed value is not valid for the focused field.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InvalidEntry {
+    /// Keeps the previous value.
+    Ignore,
+    /// Keeps the previous value and rings the terminal bell.
+    Bell,
+    /// Uses the nearest valid value, e.g. typing 13 for the month selects December.
+    Clamp,
 }
 
 /// Renders a datetime selection interactive text.
 ///
 /// prompt question is optional and not shown by default.
-/// weekday that is displayed can be turned off.
-/// date_type allows you to specify "date", "time" or "datetime"
-/// default starting time can be set if following rfc3339 format "%Y-%m-%dT%H:%M:%s%Z"
+/// weekday that is displayed can be turned off, Time and YearMonth have none.
+/// date_type allows you to specify "date", "time", "datetime", "year month" or "iso week"
+/// default starting time can be set if following rfc3339 format "%Y-%m-%dT%H:%M:%s%Z",
+/// parts that can not be edited are dropped, e.g. Date ignores the time of the default.
 /// min and max DateTime can be set to help with selection.
+/// timezone sets which zone "now" and the returned offset refer to, defaults to UTC.
+/// calendar_view shows a month grid below the date where the arrow keys move between days,
+/// with weeks starting on week_start.
+/// default_zone picks the clock the starting date comes from while keeping the timezone's offset.
+/// hour12 renders hours on a 12-hour clock with an extra AM/PM field.
+/// step sizes can be set per field to move by more than one unit on UP/DOWN.
+/// hour_range and the other field ranges limit a field on any date, min and max still apply.
 ///
-/// Note: Date values can be changed by UP/DOWN/j/k or specifying numerical values.
+/// Note: Date values can be changed by UP/DOWN/k/j or specifying numerical values.
+/// Fields are selected with LEFT/RIGHT/h/l, TAB also moves to the next field (Shift+TAB
+/// is not reported by the terminal backend, use LEFT/h to move back), as does SPACE, see advance_key.
+/// Typed digits are shown in the field until it is set, e.g. "1_" for the month.
+/// BACKSPACE removes the last typed digit, or decrements the field like DOWN if none was typed.
+/// Typed digits set the field once all of its digits are typed (4 for years, 2 for most others),
+/// or as soon as another digit would go past the field's largest value, e.g. "4" on the month
+/// sets April right away while "1" waits for a second digit.
+/// Moving on to another field or confirming sets fewer typed digits, e.g. "7" then TAB for
+/// 7 o'clock. Years typed with fewer digits use the century of two_digit_year_pivot.
+/// Months and days can be typed with a leading zero, "0" "3" selects March, further zeros after
+/// the first are ignored as no month or day is zero.
+/// K/J jump the focused field by a larger amount (10 years, 3 months, 7 days or 10 of
+/// a time unit), as console 0.11 has no PageUp/PageDown keys to bind them to.
+/// HOME/END set the focused field to its lowest/highest value, using the min/max year.
+/// DEL also clears the focused field to its lowest value, e.g. before typing a fresh one.
+/// [ and ] set the whole value to min and max.
+/// r resets the selection back to the default (or today), t jumps to today, see today_key.
+/// On the month field letters select a month by name, e.g. "f" for February or "mar" for March.
+/// Month names take priority there, so j selects January and "jun" June while k still steps.
+/// ? toggles a line listing the key bindings, which can be shown from the start with show_help.
+/// disabled_dates and disable_if make dates unavailable, stepping skips over them and Enter
+/// refuses to confirm them, weekdays_only does the same for Saturdays and Sundays.
+/// Without a terminal or with NO_COLOR set the active field is shown in brackets instead of bold.
+/// active_marker adds an underline, brackets or a caret line to the active field.
+/// interact_range selects a start and an end, where TAB switches between the two instead.
+/// Ctrl-C clears the selection and returns an error of kind `io::ErrorKind::Interrupted`.
+/// The dialog is rendered on stderr, report_stream switches to stdout.
+/// framed draws the datetime line in a box from the theme, cleared along with it.
+#[derive(Clone)]
 pub struct DateTimeSelect<'a> {
     prompt: Option<String>,
+    prompt_suffix: Option<String>,
+    prompt_on_own_line: bool,
+    prompt_separator: bool,
     default: Option<NaiveDateTime>,
     theme: &'a dyn Theme,
     weekday: bool,
+    weekday_format: WeekdayFormat,
+    weekday_position: WeekdayPosition,
     date_type: DateType,
     min: NaiveDateTime,
     max: NaiveDateTime,
+    min_offset: Option<Duration>,
+    max_offset: Option<Duration>,
     clear: bool,
+    trailing_newline: bool,
     show_match: bool,
+    hour12: bool,
+    show_seconds: bool,
+    steps: HashMap<DateField, u32>,
+    field_bounds: HashMap<DateField, (u32, u32)>,
+    show_help: bool,
+    wrap_fields: bool,
+    on_invalid_entry: InvalidEntry,
+    clamp_mode: ClampMode,
+    year_pivot: u32,
+    active_marker: Marker,
+    today_key: Key,
+    show_cursor: bool,
+    report_stream: TermTarget,
+    confirm_key: Key,
+    advance_key: Key,
+    allow_typing: bool,
+    acceleration: bool,
+    require_interaction: bool,
+    allow_empty: bool,
+    read_only: bool,
+    framed: bool,
+    month_names: bool,
+    names: &'static Names,
+    date_separator: String,
+    time_separator: String,
+    datetime_separator: String,
+    output_format: Option<String>,
+    timezone: Zone,
+    default_zone: Option<DefaultZone>,
+    calendar_view: bool,
+    week_start: Weekday,
+    disabled_dates: Vec<NaiveDate>,
+    weekdays_only: bool,
+    disable_if: Option<Rc<dyn Fn(NaiveDate) -> bool>>,
+    validator: Option<Rc<DateTimeValidator>>,
+    on_change: Option<Rc<ChangeCallback>>,
+    subsecond: Precision,
+    seconds_format: Option<SecondsFormat>,
+    emit_offset: bool,
+    #[cfg(feature = "chrono-tz")]
+    select_timezone: bool,
+}
+
+/// Same as `DateTimeSelect::new()`.
+///
+/// The inherent `default` setter shadows this, so `DateTimeSelect::default()` does not compile.
+/// Use `new()`, or `Default::default()` where a `DateTimeSelect` is expected.
+impl<'a> Default for DateTimeSelect<'a> {
+    fn default() -> DateTimeSelect<'a> {
+        DateTimeSelect::new()
+    }
 }
 
 impl<'a> DateTimeSelect<'a> {
@@ -113,14 +889,63 @@ impl<'a> DateTimeSelect<'a> {
     pub fn with_theme(theme: &'a dyn Theme) -> DateTimeSelect<'a> {
         DateTimeSelect {
             prompt: None,
+            prompt_suffix: None,
+            prompt_on_own_line: false,
+            prompt_separator: true,
             default: None,
             theme,
             weekday: true,
+            weekday_format: WeekdayFormat::Short,
+            weekday_position: WeekdayPosition::Suffix,
             date_type: DateType::DateTime,
             min: NaiveDate::from_ymd(0, 1, 1).and_hms(0, 0, 0),
             max: NaiveDate::from_ymd(9999, 12, 31).and_hms(23, 59, 59),
+            min_offset: None,
+            max_offset: None,
             clear: true,
+            trailing_newline: false,
             show_match: false,
+            hour12: false,
+            show_seconds: true,
+            steps: HashMap::new(),
+            field_bounds: HashMap::new(),
+            show_help: false,
+            wrap_fields: false,
+            on_invalid_entry: InvalidEntry::Ignore,
+            clamp_mode: ClampMode::Bound,
+            year_pivot: 69,
+            active_marker: Marker::Style,
+            today_key: Key::Char('t'),
+            show_cursor: false,
+            report_stream: TermTarget::Stderr,
+            confirm_key: Key::Enter,
+            advance_key: Key::Char(' '),
+            allow_typing: true,
+            acceleration: false,
+            require_interaction: false,
+            allow_empty: false,
+            read_only: false,
+            framed: false,
+            month_names: false,
+            names: &EN_NAMES,
+            date_separator: "-".into(),
+            time_separator: ":".into(),
+            datetime_separator: " ".into(),
+            output_format: None,
+            timezone: Zone::Utc,
+            default_zone: None,
+            calendar_view: false,
+            week_start: Weekday::Mon,
+            disabled_dates: vec![],
+            weekdays_only: false,
+            disable_if: None,
+            validator: None,
+            on_change: None,
+            subsecond: Precision::Seconds,
+            seconds_format: None,
+            emit_offset: true,
+            #[cfg(feature = "chrono-tz")]
+            select_timezone: false,
         }
     }
     /// Sets the datetime prompt.
@@ -128,273 +953,3839 @@ impl<'a> DateTimeSelect<'a> {
         self.prompt = Some(prompt.into());
         self
     }
+    /// Sets a hint shown between the prompt and the datetime, e.g. `(YYYY-MM-DD)`.
+    ///
+    /// Without a prompt the hint is shown on its own, followed by the theme's separator.
+    pub fn prompt_suffix(&mut self, suffix: &str) -> &mut Self {
+        self.prompt_suffix = Some(suffix.into());
+        self
+    }
+    /// Sets whether the theme's separator, `: ` by default, follows the prompt, defaults to true.
+    ///
+    /// Without it the prompt and the datetime are joined by a space, e.g. for a prompt ending
+    /// in a question mark or a suffix like `(YYYY-MM-DD)`.
+    pub fn prompt_separator(&mut self, val: bool) -> &mut Self {
+        self.prompt_separator = val;
+        self
+    }
+    /// Sets whether the prompt is shown on its own line above the datetime.
+    pub fn prompt_on_own_line(&mut self, val: bool) -> &mut Self {
+        self.prompt_on_own_line = val;
+        self
+    }
     /// Sets default time to start with.
+    ///
+    /// Panics if the string does not match rfc3339, see `try_default` for a fallible version.
     pub fn default(&mut self, datetime: &str) -> &mut Self {
-        self.default = Some(DateTime::parse_from_rfc3339(datetime).expect("date format must match rfc3339").naive_local());
+        self.try_default(datetime).unwrap_or_else(|err| panic!("{}", err))
+    }
+    /// Like `default` but returns an error instead of panicking on bad input.
+    pub fn try_default(&mut self, datetime: &str) -> Result<&mut Self, DateTimeError> {
+        self.default = Some(parse_datetime(datetime)?);
+        Ok(self)
+    }
+    /// Sets default time to start with from an environment variable holding an rfc3339 string.
+    ///
+    /// The default is left unchanged if the variable is unset or does not match rfc3339.
+    pub fn default_from_env(&mut self, var: &str) -> &mut Self {
+        if let Some(datetime) = env::var(var).ok().and_then(|val| parse_datetime(&val).ok()) {
+            self.default = Some(datetime);
+        }
+        self
+    }
+    /// Sets default time to start with from an existing `NaiveDateTime`.
+    pub fn default_datetime(&mut self, datetime: NaiveDateTime) -> &mut Self {
+        self.default = Some(datetime);
         self
     }
     /// Sets whether to show weekday or not.
+    ///
+    /// Time and YearMonth never show a weekday, as they have no day.
     pub fn weekday(&mut self, val: bool) -> &mut Self {
         self.weekday = val;
         self
     }
+    /// Sets whether the weekday is shown abbreviated or in full, defaults to abbreviated.
+    pub fn weekday_format(&mut self, val: WeekdayFormat) -> &mut Self {
+        self.weekday_format = val;
+        self
+    }
+    /// Sets whether the weekday is shown before or after the date, defaults to after.
+    pub fn weekday_position(&mut self, val: WeekdayPosition) -> &mut Self {
+        self.weekday_position = val;
+        self
+    }
     /// Sets date selector to date, time, or datetime format.
     pub fn date_type(&mut self, val: DateType) -> &mut Self {
         self.date_type = val;
         self
     }
-    /// Sets min value for Date or DateTime.
+    /// Sets min value for Date, Time or DateTime.
+    ///
+    /// Takes rfc3339 or a plain date like `2024-01-01`, which starts at 00:00:00.
+    /// For Time a min after the max is a window across midnight, e.g. 22:00 to 06:00, the other
+    /// types return `DateTimeError::InvalidRange` when interacting.
+    /// Panics on bad input, see `try_min` for a fallible version.
     pub fn min(&mut self, val: &str) -> &mut Self {
-        self.min = DateTime::parse_from_rfc3339(val).expect("date format must match rfc3339").naive_local();
-        assert!(self.max >= self.min, "maximum must be larger than minimum");
-        self
+        self.try_min(val).unwrap_or_else(|err| panic!("{}", err))
     }
-    /// Sets max value for Date or DateTime.
+    /// Like `min` but returns an error instead of panicking on bad input.
+    pub fn try_min(&mut self, val: &str) -> Result<&mut Self, DateTimeError> {
+        self.min = parse_bound_date(val, false)?;
+        Ok(self)
+    }
+    /// Sets max value for Date, Time or DateTime.
+    ///
+    /// Takes rfc3339 or a plain date like `2024-12-31`, which ends at 23:59:59.
+    /// For Time a max before the min is a window across midnight, see `min`.
+    /// Panics on bad input, see `try_max` for a fallible version.
     pub fn max(&mut self, val: &'a str) -> &mut Self {
-        self.max = DateTime::parse_from_rfc3339(val).expect("date format must match rfc3339").naive_local();
-        assert!(self.max >= self.min, "maximum must be larger than minimum");
+        self.try_max(val).unwrap_or_else(|err| panic!("{}", err))
+    }
+    /// Like `max` but returns an error instead of panicking on bad input.
+    pub fn try_max(&mut self, val: &str) -> Result<&mut Self, DateTimeError> {
+        self.max = parse_bound_date(val, true)?;
+        Ok(self)
+    }
+    /// Sets the minimum as an offset from the start of today, e.g. `Duration::zero()` for no earlier than today.
+    ///
+    /// Today is taken when interacting, in the same zone as the default. A later `min` still applies.
+    pub fn min_offset(&mut self, val: Duration) -> &mut Self {
+        self.min_offset = Some(val);
+        self
+    }
+    /// Sets the maximum as an offset from the start of today, e.g. `Duration::days(90)`.
+    ///
+    /// Today is taken when interacting, in the same zone as the default. An earlier `max` still applies.
+    pub fn max_offset(&mut self, val: Duration) -> &mut Self {
+        self.max_offset = Some(val);
         self
     }
     /// Sets whether to clear inputs from terminal.
+    ///
+    /// When not cleared the confirmed value is left behind as `prompt: value`.
     pub fn clear(&mut self, val: bool) -> &mut Self {
         self.clear = val;
         self
     }
-    /// Sets whether to show match string or not.
+    /// Sets whether an empty line follows the confirmed value when not clearing, defaults to false.
+    ///
+    /// Leaves the cursor on a fresh line with styling reset, so output piped after it is clean.
+    pub fn trailing_newline(&mut self, val: bool) -> &mut Self {
+        self.trailing_newline = val;
+        self
+    }
+    /// Sets whether to show the typed digits on a line below, in addition to the field itself.
     pub fn show_match(&mut self, val: bool) -> &mut Self {
         self.show_match = val;
         self
     }
-
-    fn check_date(&self, val: NaiveDateTime) -> NaiveDateTime {
-        min(max(val, self.min), self.max)
+    /// Sets whether to show the key binding help line, it can also be toggled with `?`.
+    pub fn show_help(&mut self, val: bool) -> &mut Self {
+        self.show_help = val;
+        self
     }
-
-    fn terminal_format(&self, val: NaiveDateTime, pos: isize) -> String {
-        match self.date_type {
-            DateType::Date => format!(
-                "{}-{:02}-{:02}",
-                if pos == 0 { style(val.year()).bold() } else { style(val.year()).dim() },
-                if pos == 1 { style(val.month()).bold() } else { style(val.month()).dim() },
-                if pos == 2 { style(val.day()).bold() } else { style(val.day()).dim() },
-            ),
-            DateType::Time => format!(
-                "{:02}:{:02}:{:02}",
-                if pos == 0 { style(val.hour()).bold() } else { style(val.hour()).dim() },
-                if pos == 1 { style(val.minute()).bold() } else { style(val.minute()).dim() },
-                if pos == 2 { style(val.second()).bold() } else { style(val.second()).dim() },
-            ),
-            DateType::DateTime => format!(
-                "{}-{:02}-{:02} {:02}:{:02}:{:02}",
-                if pos == 0 { style(val.year()).bold() } else { style(val.year()).dim() },
-                if pos == 1 { style(val.month()).bold() } else { style(val.month()).dim() },
-                if pos == 2 { style(val.day()).bold() } else { style(val.day()).dim() },
-                if pos == 3 { style(val.hour()).bold() } else { style(val.hour()).dim() },
-                if pos == 4 { style(val.minute()).bold() } else { style(val.minute()).dim() },
-                if pos == 5 { style(val.second()).bold() } else { style(val.second()).dim() },
-            ),
-        }
+    /// Sets whether stepping a field wraps around within that field.
+    ///
+    /// By default stepping carries into neighboring fields, so going up from 59 seconds
+    /// increments the minute. With wrapping enabled 59 seconds goes back to 00 and leaves the
+    /// rest untouched. Days wrap within the current month and months wrap within the current
+    /// year, with the day clamped to the length of the new month. Years never wrap.
+    pub fn wrap_fields(&mut self, val: bool) -> &mut Self {
+        self.wrap_fields = val;
+        self
     }
-
-    /// Enables user interaction and returns the result.
+    /// Sets whether to display the month as a short name (Jan, Feb, ...) instead of a number.
+    pub fn month_names(&mut self, val: bool) -> &mut Self {
+        self.month_names = val;
+        self
+    }
+    /// Sets the locale used for weekday and month names, e.g. "de" or "fr_FR".
     ///
-    /// The dialog is rendered on stderr.
-    pub fn interact(&self) -> io::Result<String> {
-        self.interact_on(&Term::stderr())
+    /// Supported languages are English, German, Spanish, French, Italian, Dutch and Portuguese.
+    /// Unknown locales fall back to English.
+    pub fn locale(&mut self, loc: &str) -> &mut Self {
+        self.names = names_for_locale(loc);
+        self
     }
-    /// Like `interact` but allows a specific terminal to be set.
-    fn interact_on(&self, term: &Term) -> io::Result<String> {
-        let mut date_val = self.default.unwrap_or_else(|| {
-            // Current date in UTC is used as default time if override not set.
-            Utc::today().and_hms(0, 0, 0).naive_utc()
-        });
-
-        date_val = self.check_date(date_val);
-        let mut render = TermThemeRenderer::new(term, self.theme);
-
-        // Set vars for handling changing datetimes.
-        let mut pos = 0;
-        let max_pos = match self.date_type {
-            DateType::Date => 2,
-            DateType::Time => 2,
-            DateType::DateTime => 5,
-        };
-        let mut digits: Vec<u32> = Vec::with_capacity(4);
-
-        loop {
-            // Styling is added to highlight pos being changed.
-            let date_str = self.terminal_format(date_val, pos);
-
-            // Add weekday if specified.
-            let date_str = match &self.weekday {
-                true => format!("{}, {:?}", date_str, date_val.weekday()),
-                false => date_str,
-            };
-
-            // Render current state of datetime string.
-            render.datetime(&self.prompt, &date_str)?;
-
-            // Display typed numbers if show_match is true.
-            if self.show_match {
-                let str_num: Vec<String> = digits.iter().map(|c| c.to_string()).collect();
-                term.write_line(&str_num.join(""))?;
-            }
-
-            match term.read_key()? {
-                Key::Enter => {
-                    // Clean up terminal.
-                    if self.clear {
-                        render.clear()?
-                    }
-                    if self.show_match {
-                        term.clear_last_lines(1)?;
-                    }
-                    // Clean up formatting of returned string.
-                    let date_str = match self.date_type {
-                        DateType::Date => date_val.format("%Y-%m-%d").to_string(),
-                        DateType::Time => date_val.format("%H:%M:%S").to_string(),
-                        DateType::DateTime => Utc.from_utc_datetime(&date_val).to_rfc3339_opts(SecondsFormat::Secs, true),
-                    };
-                    return Ok(date_str);
-                }
-                Key::ArrowRight | Key::Char('l') => {
-                    pos = if pos == max_pos { 0 } else { pos + 1 };
-                    digits.clear();
-                }
-                Key::ArrowLeft | Key::Char('h') => {
-                    pos = if pos == 0 { max_pos } else { pos - 1 };
-                    digits.clear();
+    /// Sets the separator between year, month and day, defaults to `-`.
+    pub fn date_separator(&mut self, val: &str) -> &mut Self {
+        self.date_separator = val.into();
+        self
+    }
+    /// Sets the separator between hours, minutes and seconds, defaults to `:`.
+    pub fn time_separator(&mut self, val: &str) -> &mut Self {
+        self.time_separator = val.into();
+        self
+    }
+    /// Sets the separator between the date and the time, defaults to a space.
+    pub fn datetime_separator(&mut self, val: &str) -> &mut Self {
+        self.datetime_separator = val.into();
+        self
+    }
+    /// Sets the strftime style format of the string returned by `interact`.
+    ///
+    /// When not set Date returns `%Y-%m-%d`, Time returns `%H:%M:%S` and DateTime returns rfc3339.
+    pub fn output_format(&mut self, fmt: &str) -> &mut Self {
+        self.output_format = Some(fmt.into());
+        self
+    }
+    /// Sets the timezone used for the current date and the offset of the returned datetime.
+    ///
+    /// Editing always happens on the wall clock time in this zone, defaults to UTC.
+    pub fn timezone(&mut self, val: Zone) -> &mut Self {
+        self.timezone = val;
+        self
+    }
+    /// Sets whether to show a month calendar below a Date or DateTime.
+    ///
+    /// While the day field is focused, LEFT/RIGHT move by a day and UP/DOWN by a week,
+    /// crossing into the adjacent month where needed. TAB moves on to the other fields.
+    pub fn calendar_view(&mut self, val: bool) -> &mut Self {
+        self.calendar_view = val;
+        self
+    }
+    /// Sets the first day of the week in the calendar, defaults to Monday.
+    pub fn week_start(&mut self, val: Weekday) -> &mut Self {
+        self.week_start = val;
+        self
+    }
+    /// Sets dates that can not be selected.
+    ///
+    /// UP/DOWN skip over these dates and Enter refuses to confirm them.
+    pub fn disabled_dates(&mut self, dates: Vec<NaiveDate>) -> &mut Self {
+        self.disabled_dates = dates;
+        self
+    }
+    /// Sets whether only Monday to Friday can be selected.
+    ///
+    /// Stepping the day skips weekends in the direction of the step, e.g. UP on a Friday
+    /// lands on the Monday after, and Enter refuses to confirm a Saturday or Sunday.
+    pub fn weekdays_only(&mut self, val: bool) -> &mut Self {
+        self.weekdays_only = val;
+        self
+    }
+    /// Registers a predicate for dates that can not be selected.
+    ///
+    /// Can be called multiple times, a date is disabled if any of the predicates returns true.
+    pub fn disable_if<F: Fn(NaiveDate) -> bool + 'static>(&mut self, disabled: F) -> &mut Self {
+        let old_disabled = self.disable_if.take();
+        self.disable_if = Some(Rc::new(move |date: NaiveDate| -> bool {
+            if let Some(old) = old_disabled.as_ref() {
+                if old(date) {
+                    return true;
+                }
+            }
+            disabled(date)
+        }));
+        self
+    }
+    /// Registers a validator that is run when Enter is pressed.
+    ///
+    /// If it returns an error the selection stays open and the error is shown below it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # extern crate chrono;
+    /// # extern crate dialoguer;
+    /// # use chrono::Timelike;
+    /// # use dialoguer::DateTimeSelect;
+    /// # fn main() {
+    /// let datetime = DateTimeSelect::new()
+    ///     .with_prompt("Meeting start")
+    ///     .validate_with(|val: &chrono::NaiveDateTime| -> Result<(), &str> {
+    ///         if val.minute() == 0 && val.second() == 0 {
+    ///             Ok(())
+    ///         } else {
+    ///             Err("Meetings start on the hour")
+    ///         }
+    ///     })
+    ///     .interact()
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub fn validate_with<F, E>(&mut self, validator: F) -> &mut Self
+    where
+        F: Fn(&NaiveDateTime) -> Result<(), E> + 'static,
+        E: fmt::Display,
+    {
+        let old_validator_func = self.validator.take();
+        self.validator = Some(Rc::new(move |value: &NaiveDateTime| -> Option<String> {
+            if let Some(old) = old_validator_func.as_ref() {
+                if let Some(err) = old(value) {
+                    return Some(err);
+                }
+            }
+            match validator(value) {
+                Ok(()) => None,
+                Err(err) => Some(err.to_string()),
+            }
+        }));
+        self
+    }
+    /// Registers a callback that gets the value when it is first shown and after every edit.
+    ///
+    /// Keys that leave the value as it is, like moving between the fields, do not call it.
+    /// The callback should not write to the lines of the selection, they are cleared on redraw.
+    pub fn on_change<F>(&mut self, on_change: F) -> &mut Self
+    where
+        F: Fn(&NaiveDateTime) + 'static,
+    {
+        self.on_change = Some(Rc::new(on_change));
+        self
+    }
+    /// Sets whether the current date used when no default is set comes from the local clock.
+    ///
+    /// When off, the date is taken from the configured `timezone`, which is UTC unless changed.
+    pub fn default_now_local(&mut self, val: bool) -> &mut Self {
+        self.default_zone = if val { Some(DefaultZone::Local) } else { None };
+        self
+    }
+    /// Sets the clock the current date comes from when no default is set.
+    ///
+    /// Only the starting value changes, the returned offset still follows `timezone`.
+    pub fn default_zone(&mut self, val: DefaultZone) -> &mut Self {
+        self.default_zone = Some(val);
+        self
+    }
+    /// Sets whether to show a field to pick a named timezone for Time or DateTime.
+    ///
+    /// UP/DOWN on this field cycle through a list of common IANA timezones, starting from the
+    /// configured `timezone`. The returned rfc3339 offset matches the chosen zone and date.
+    #[cfg(feature = "chrono-tz")]
+    pub fn select_timezone(&mut self, val: bool) -> &mut Self {
+        self.select_timezone = val;
+        self
+    }
+    /// Sets whether fractions of a second can be edited for Time or DateTime.
+    ///
+    /// The returned value then carries milliseconds or nanoseconds as well.
+    pub fn subsecond(&mut self, val: Precision) -> &mut Self {
+        self.subsecond = val;
+        self
+    }
+    /// Sets how seconds are written in the result of a DateTime.
+    ///
+    /// Defaults to the precision set with `subsecond`.
+    pub fn seconds_format(&mut self, val: SecondsFormat) -> &mut Self {
+        self.seconds_format = Some(val);
+        self
+    }
+    /// Sets whether the result of a DateTime ends in its offset or `Z`, defaults to true.
+    ///
+    /// Without it a naive timestamp like `2024-03-07T12:00:00` is returned.
+    pub fn emit_offset(&mut self, val: bool) -> &mut Self {
+        self.emit_offset = val;
+        self
+    }
+    /// Sets how the field being edited is marked, for readers that can not rely on the bold styling.
+    ///
+    /// Without colors the active field gets brackets unless the caret is used.
+    pub fn active_marker(&mut self, val: Marker) -> &mut Self {
+        self.active_marker = val;
+        self
+    }
+    /// Sets whether Enter is refused until the user moved to another field or changed the value.
+    ///
+    /// Keeps the default from being accepted by accident.
+    pub fn require_interaction(&mut self, val: bool) -> &mut Self {
+        self.require_interaction = val;
+        self
+    }
+    /// Sets whether the selection starts without a value, shown as dashes, defaults to false.
+    ///
+    /// The first edit enters the default (or today) and applies it. Confirming while still empty
+    /// makes `interact_optional` return `None`, the other methods return the default instead.
+    pub fn allow_empty(&mut self, val: bool) -> &mut Self {
+        self.allow_empty = val;
+        self
+    }
+    /// Sets whether the datetime line is drawn in a box, defaults to false.
+    ///
+    /// The borders come from the theme, see `Theme::format_datetime_border`. The caret line of
+    /// `Marker::Caret` is drawn inside the box, below the datetime.
+    pub fn framed(&mut self, val: bool) -> &mut Self {
+        self.framed = val;
+        self
+    }
+    /// Sets whether the value is only shown, defaults to false.
+    ///
+    /// No field is highlighted and all editing keys are ignored, Enter or Esc return the value as is.
+    pub fn read_only(&mut self, val: bool) -> &mut Self {
+        self.read_only = val;
+        self
+    }
+    /// Sets whether holding UP/DOWN steps faster after a while, defaults to false.
+    ///
+    /// Off by default so every key press makes exactly one step, e.g. for scripted input.
+    pub fn acceleration(&mut self, val: bool) -> &mut Self {
+        self.acceleration = val;
+        self
+    }
+    /// Sets whether values can be typed as digits, defaults to true.
+    ///
+    /// Without typing fields only change with the arrow keys and the other bindings.
+    pub fn allow_typing(&mut self, val: bool) -> &mut Self {
+        self.allow_typing = val;
+        self
+    }
+    /// Sets the key that confirms the selection, defaults to Enter.
+    ///
+    /// Enter has no effect when another key is set.
+    pub fn confirm_key(&mut self, val: Key) -> &mut Self {
+        self.confirm_key = val;
+        self
+    }
+    /// Sets a key that moves to the next field and wraps around, defaults to Space.
+    ///
+    /// A fallback for terminals that mangle the arrow keys, it confirms instead if it is also the `confirm_key`.
+    pub fn advance_key(&mut self, val: Key) -> &mut Self {
+        self.advance_key = val;
+        self
+    }
+    /// Sets the key that jumps back to today, defaults to `t`.
+    pub fn today_key(&mut self, val: Key) -> &mut Self {
+        self.today_key = val;
+        self
+    }
+    /// Sets whether the terminal cursor stays visible while selecting, defaults to false.
+    ///
+    /// A hidden cursor is shown again however the selection ends.
+    pub fn show_cursor(&mut self, val: bool) -> &mut Self {
+        self.show_cursor = val;
+        self
+    }
+    /// Sets whether `interact` and the other methods without a terminal render on stdout or stderr.
+    ///
+    /// Defaults to stderr.
+    pub fn report_stream(&mut self, val: TermTarget) -> &mut Self {
+        self.report_stream = val;
+        self
+    }
+    /// Sets where two digit years switch centuries, defaults to 69.
+    ///
+    /// Fewer than 4 year digits are used once the field is left or Enter is pressed. One or two
+    /// digits below the pivot are in the 2000s and the others in the 1900s, so `88` is 1988 and
+    /// `05` is 2005 by default. Three digits are used as they are.
+    pub fn two_digit_year_pivot(&mut self, val: u32) -> &mut Self {
+        self.year_pivot = val;
+        self
+    }
+    /// Sets how an edit that leaves `min`/`max` is brought back into range, defaults to `ClampMode::Bound`.
+    pub fn clamp_mode(&mut self, val: ClampMode) -> &mut Self {
+        self.clamp_mode = val;
+        self
+    }
+    /// Sets how typed values that are out of range for a field are handled.
+    ///
+    /// The default is to ignore them and keep the previous value.
+    pub fn on_invalid_entry(&mut self, val: InvalidEntry) -> &mut Self {
+        self.on_invalid_entry = val;
+        self
+    }
+    /// Sets whether to use a 12-hour clock with an AM/PM field for Time or DateTime.
+    ///
+    /// The result uses the same clock, e.g. "01:00:00 PM", DateTime is then no longer rfc3339.
+    pub fn hour12(&mut self, val: bool) -> &mut Self {
+        self.hour12 = val;
+        self
+    }
+    /// Sets whether the seconds can be edited for Time or DateTime.
+    ///
+    /// Hidden seconds, and fractions of a second, are zero in the returned value.
+    pub fn show_seconds(&mut self, val: bool) -> &mut Self {
+        self.show_seconds = val;
+        self
+    }
+    /// Sets how far UP/DOWN move the given field, defaults to 1.
+    ///
+    /// Has no effect on the AM/PM field which always toggles.
+    /// Panics on a step of zero, see `try_step` for a fallible version.
+    pub fn step(&mut self, field: DateField, val: u32) -> &mut Self {
+        self.try_step(field, val).unwrap_or_else(|err| panic!("{}", err))
+    }
+    /// Like `step` but returns an error instead of panicking on a step of zero.
+    pub fn try_step(&mut self, field: DateField, val: u32) -> Result<&mut Self, DateTimeError> {
+        if val == 0 {
+            return Err(DateTimeError::ZeroStep);
+        }
+        self.steps.insert(field, val);
+        Ok(self)
+    }
+    /// Sets how many minutes UP/DOWN move the minute field.
+    pub fn step_minutes(&mut self, val: u32) -> &mut Self {
+        self.step(DateField::Minute, val)
+    }
+    /// Sets how many seconds UP/DOWN move the second field.
+    pub fn step_seconds(&mut self, val: u32) -> &mut Self {
+        self.step(DateField::Second, val)
+    }
+    /// Keeps a field within `low..=high` on any date, e.g. hours 9 to 17 for business hours.
+    ///
+    /// Supported for the month, day, hour, minute and second, hours count on a 24-hour clock.
+    /// Stepping or typing past the range lands on its end. The range is applied before `min`
+    /// and `max`, so where both constrain a value the stricter one wins.
+    /// Panics if `low` is larger than `high`, see `try_field_bounds` for a fallible version.
+    pub fn field_bounds(&mut self, field: DateField, low: u32, high: u32) -> &mut Self {
+        self.try_field_bounds(field, low, high).unwrap_or_else(|err| panic!("{}", err))
+    }
+    /// Like `field_bounds` but returns an error instead of panicking when `low` is larger than `high`.
+    pub fn try_field_bounds(&mut self, field: DateField, low: u32, high: u32) -> Result<&mut Self, DateTimeError> {
+        if low > high {
+            return Err(DateTimeError::InvalidFieldBounds);
+        }
+        self.field_bounds.insert(field, (low, high));
+        Ok(self)
+    }
+    /// Keeps the hour within `low..=high` on a 24-hour clock.
+    pub fn hour_range(&mut self, low: u32, high: u32) -> &mut Self {
+        self.field_bounds(DateField::Hour, low, high)
+    }
+    /// Keeps the minute within `low..=high`.
+    pub fn minute_range(&mut self, low: u32, high: u32) -> &mut Self {
+        self.field_bounds(DateField::Minute, low, high)
+    }
+    /// Keeps the second within `low..=high`.
+    pub fn second_range(&mut self, low: u32, high: u32) -> &mut Self {
+        self.field_bounds(DateField::Second, low, high)
+    }
+
+    fn fields(&self) -> Vec<DateField> {
+        let mut fields = match self.date_type {
+            DateType::Date => vec![DateField::Year, DateField::Month, DateField::Day],
+            DateType::Time => vec![DateField::Hour, DateField::Minute, DateField::Second],
+            DateType::DateTime => vec![DateField::Year, DateField::Month, DateField::Day, DateField::Hour, DateField::Minute, DateField::Second],
+            DateType::YearMonth => vec![DateField::Year, DateField::Month],
+            DateType::IsoWeek => vec![DateField::IsoYear, DateField::Week, DateField::Weekday],
+        };
+        if !self.show_seconds {
+            fields.retain(|&field| field != DateField::Second);
+        }
+        match self.subsecond {
+            _ if !self.shows_time() || !self.show_seconds => {}
+            Precision::Seconds => {}
+            Precision::Millis => fields.push(DateField::Millisecond),
+            Precision::Nanos => fields.push(DateField::Nanosecond),
+        }
+        if self.hour12 && self.shows_time() {
+            fields.push(DateField::Meridiem);
+        }
+        #[cfg(feature = "chrono-tz")]
+        {
+            if self.select_timezone && self.shows_time() {
+                fields.push(DateField::Timezone);
+            }
+        }
+        fields
+    }
+
+    /// Returns whether the time of day is part of the selection.
+    fn shows_time(&self) -> bool {
+        self.date_type == DateType::Time || self.date_type == DateType::DateTime
+    }
+
+    /// Returns whether a single day is selected, through the day of the month or the ISO weekday.
+    fn shows_day(&self) -> bool {
+        self.date_type != DateType::Time && self.date_type != DateType::YearMonth
+    }
+
+    /// Returns the lowest and highest selectable value, narrowing `min`/`max` by any offsets from today.
+    fn bounds(&self) -> (NaiveDateTime, NaiveDateTime) {
+        let today = self.today();
+        let low = self.min_offset.map_or(self.min, |offset| max(self.min, today + offset));
+        let high = self.max_offset.map_or(self.max, |offset| min(self.max, today + offset));
+        (low, high)
+    }
+
+    /// Keeps each field with a range from `field_bounds` within it.
+    fn check_field_bounds(&self, val: NaiveDateTime) -> NaiveDateTime {
+        // The month goes first, the days available depend on it.
+        let fields = [DateField::Month, DateField::Day, DateField::Hour, DateField::Minute, DateField::Second];
+        fields.iter().fold(val, |val, field| {
+            let (low, high) = match self.field_bounds.get(field) {
+                Some(&bounds) => bounds,
+                None => return val,
+            };
+            match *field {
+                DateField::Month => with_month_clamped(val, min(max(val.month(), low), high)),
+                DateField::Day => {
+                    let last_day = days_in_month(val.year(), val.month());
+                    val.with_day(min(min(max(val.day(), low), high), last_day)).unwrap_or(val)
+                }
+                DateField::Hour => val.with_hour(min(max(val.hour(), low), min(high, 23))).unwrap_or(val),
+                DateField::Minute => val.with_minute(min(max(val.minute(), low), min(high, 59))).unwrap_or(val),
+                _ => val.with_second(min(max(val.second(), low), min(high, 59))).unwrap_or(val),
+            }
+        })
+    }
+
+    /// Keeps a value within `min` and `max`, comparing only the parts the date type edits.
+    fn check_date(&self, val: NaiveDateTime) -> NaiveDateTime {
+        let val = self.check_field_bounds(val);
+        let (low, high) = self.bounds();
+        match self.date_type {
+            // The date of a Time is not edited, so it must not push the time around.
+            DateType::Time => val.date().and_time(clamp_time(val.time(), low.time(), high.time())),
+            DateType::Date | DateType::YearMonth | DateType::IsoWeek => {
+                min(max(val.date(), low.date()), high.date()).and_time(val.time())
+            }
+            DateType::DateTime => min(max(val, low), high),
+        }
+    }
+
+    /// Brings a value back into range after `field` was edited, following the clamp mode.
+    fn check_field(&self, val: NaiveDateTime, field: DateField) -> NaiveDateTime {
+        let (low, high) = self.bounds();
+        let checked = self.check_date(val);
+        // Times are compared without their date, leaving no other field to keep.
+        if self.clamp_mode == ClampMode::Bound || self.date_type == DateType::Time || checked == val {
+            return checked;
+        }
+        let (bound, up) = if val < low { (low, true) } else { (high, false) };
+        // Take the field from the bound, or one past it if the other fields are still out of range.
+        let snapped = match field {
+            DateField::Year => val.with_year(bound.year()).or_else(|| val.with_day(28).and_then(|val| val.with_year(bound.year()))),
+            DateField::Month => Some(with_month_clamped(val, bound.month())),
+            DateField::Day => val.with_day(bound.day()),
+            DateField::Hour => val.with_hour(bound.hour()),
+            DateField::Minute => val.with_minute(bound.minute()),
+            DateField::Second => val.with_second(bound.second()),
+            DateField::Millisecond | DateField::Nanosecond => val.with_nanosecond(bound.nanosecond()),
+            DateField::Meridiem | DateField::Timezone => None,
+            DateField::IsoYear => with_iso_year_clamped(val, bound.iso_week().year()),
+            DateField::Week => with_iso_week(val, val.iso_week().year(), bound.iso_week().week(), val.weekday().number_from_monday()),
+            DateField::Weekday => with_iso_week(val, val.iso_week().year(), val.iso_week().week(), bound.weekday().number_from_monday()),
+        };
+        snapped
+            .into_iter()
+            .flat_map(|snapped| vec![snapped, self.shift_field(snapped, field, if up { 1 } else { -1 })])
+            .find(|&val| val >= low && val <= high)
+            .unwrap_or_else(|| self.check_date(val))
+    }
+
+    /// Formats the datetime with the field at `pos` highlighted, showing the digits typed into it so far.
+    fn terminal_format(&self, val: NaiveDateTime, zone: Zone, pos: usize, digits: &[u32], plain: bool) -> String {
+        let mut date_str = String::new();
+        for (idx, (separator, value)) in self.typed_values(val, zone, pos, digits).iter().enumerate() {
+            date_str.push_str(separator);
+            write_field(self.theme, &mut date_str, value, idx == pos, plain, self.active_marker);
+        }
+        date_str
+    }
+
+    /// Returns the column and width of the field at `pos` in the unstyled datetime.
+    fn active_span(&self, val: NaiveDateTime, zone: Zone, pos: usize, digits: &[u32]) -> (usize, usize) {
+        let mut column = 0;
+        for (idx, (separator, value)) in self.typed_values(val, zone, pos, digits).iter().enumerate() {
+            column += measure_text_width(separator);
+            if idx == pos {
+                return (column, measure_text_width(value));
+            }
+            column += measure_text_width(value);
+        }
+        (column, 0)
+    }
+
+    /// Like `field_values`, with the field at `pos` replaced by the typed digits padded with `_`.
+    fn typed_values(&self, val: NaiveDateTime, zone: Zone, pos: usize, digits: &[u32]) -> Vec<(&str, String)> {
+        let mut values = self.field_values(val, zone);
+        if let (false, Some(&field)) = (digits.is_empty(), self.fields().get(pos)) {
+            let typed: String = digits.iter().map(|digit| digit.to_string()).collect();
+            values[pos].1 = format!("{:_<width$}", typed, width = digits_needed(field));
+        }
+        values
+    }
+
+    /// Returns each field's value as displayed, together with the separator written before it.
+    fn field_values(&self, val: NaiveDateTime, zone: Zone) -> Vec<(&str, String)> {
+        let mut values = vec![];
+        let mut prev = None;
+        for field in self.fields() {
+            let separator = match (prev, field) {
+                (None, _) => "",
+                (_, DateField::Meridiem) | (_, DateField::Timezone) => " ",
+                (_, DateField::Millisecond) | (_, DateField::Nanosecond) => ".",
+                (Some(DateField::Day), DateField::Hour) => &self.datetime_separator,
+                (_, DateField::Month) | (_, DateField::Day) | (_, DateField::Week) | (_, DateField::Weekday) => {
+                    &self.date_separator
+                }
+                _ => &self.time_separator,
+            };
+            let value = match field {
+                // Years are padded to keep the line from shifting when stepping below 1000.
+                DateField::Year => format!("{:04}", val.year()),
+                DateField::Month if self.month_names => self.names.short_months[val.month0() as usize].to_string(),
+                DateField::Month => format!("{:02}", val.month()),
+                DateField::Day => format!("{:02}", val.day()),
+                DateField::Hour if self.hour12 => format!("{:02}", val.hour12().1),
+                DateField::Hour => format!("{:02}", val.hour()),
+                DateField::Minute => format!("{:02}", val.minute()),
+                DateField::Second => format!("{:02}", val.second()),
+                DateField::Millisecond => format!("{:03}", val.nanosecond() % 1_000_000_000 / 1_000_000),
+                DateField::Nanosecond => format!("{:09}", val.nanosecond() % 1_000_000_000),
+                DateField::Meridiem => if val.hour12().0 { "PM" } else { "AM" }.to_string(),
+                DateField::Timezone => zone.to_string(),
+                DateField::IsoYear => format!("{:04}", val.iso_week().year()),
+                DateField::Week => format!("W{:02}", val.iso_week().week()),
+                DateField::Weekday => val.weekday().number_from_monday().to_string(),
+            };
+            values.push((separator, value));
+            prev = Some(field);
+        }
+        values
+    }
+
+    /// Returns whether the date of a value has been disabled.
+    fn is_disabled(&self, val: NaiveDateTime) -> bool {
+        if !self.shows_day() {
+            return false;
+        }
+        let date = val.date();
+        let weekend = date.weekday() == Weekday::Sat || date.weekday() == Weekday::Sun;
+        (self.weekdays_only && weekend)
+            || self.disabled_dates.contains(&date)
+            || match self.disable_if {
+                Some(ref disabled) => disabled(date),
+                None => false,
+            }
+    }
+
+    /// Applies a step, repeating it while it lands on a disabled date and `min`/`max` allow.
+    fn skip_disabled<F>(&self, val: NaiveDateTime, field: DateField, step: F) -> NaiveDateTime
+    where
+        F: Fn(NaiveDateTime) -> NaiveDateTime,
+    {
+        let mut current = self.check_field(step(val), field);
+        for _ in 0..MAX_SKIPPED {
+            if !self.is_disabled(current) {
+                break;
+            }
+            let next = self.check_field(step(current), field);
+            // Stop at a bound, or when wrapping fields brought us back to the start.
+            if next == current || next == val {
+                break;
+            }
+            current = next;
+        }
+        current
+    }
+
+    /// Steps the edited value like `skip_disabled`, with feedback when `min`/`max` stopped the step.
+    fn step_bounded<F>(&self, state: &mut DateTimeSelectState, field: DateField, step: F) -> KeyOutcome
+    where
+        F: Fn(NaiveDateTime) -> NaiveDateTime,
+    {
+        let current = state.date_val;
+        let stepped = step(current);
+        let bounded = self.check_field(stepped, field);
+        state.digits.clear();
+        // A step past min/max lands on the bound, unless the value already is at it or the bound lies behind.
+        let towards = if stepped > current { bounded > current } else { bounded < current };
+        if bounded == stepped || towards {
+            state.date_val = self.skip_disabled(current, field, step);
+            return KeyOutcome::Continue;
+        }
+        state.feedback = Some(if stepped > bounded { "Already at the maximum" } else { "Already at the minimum" }.into());
+        KeyOutcome::Bell
+    }
+
+    /// Returns whether the month calendar is shown below the datetime.
+    fn shows_calendar(&self) -> bool {
+        self.calendar_view && self.fields().contains(&DateField::Day)
+    }
+
+    /// Renders the month around the selected day as a grid of weeks starting on `week_start`.
+    fn calendar_lines(&self, val: NaiveDateTime) -> Vec<String> {
+        let first = self.week_start.num_days_from_monday() as usize;
+        let header: Vec<String> = (0..7)
+            .map(|idx| self.names.short_weekdays[(first + idx) % 7])
+            .map(|name| format!(" {:>2} ", name.chars().take(2).collect::<String>()))
+            .collect();
+        let mut lines = vec![header.join("")];
+
+        let offset = (NaiveDate::from_ymd(val.year(), val.month(), 1).weekday().num_days_from_monday() + 7
+            - self.week_start.num_days_from_monday())
+            % 7;
+        let days = days_in_month(val.year(), val.month());
+        let mut week = " ".repeat(4 * offset as usize);
+        for day in 1..=days {
+            // Writing to a String can not fail.
+            let _ = self.theme.format_calendar_day(&mut week, day, day == val.day());
+            if (offset + day - 1) % 7 == 6 || day == days {
+                lines.push(week.trim_end().to_string());
+                week = String::new();
+            }
+        }
+        lines
+    }
+
+    /// Steps a single field of the datetime up or down by its configured step.
+    fn step_field(&self, val: NaiveDateTime, field: DateField, up: bool) -> NaiveDateTime {
+        let amount = i64::from(self.steps.get(&field).cloned().unwrap_or(1));
+        self.shift_field(val, field, if up { amount } else { -amount })
+    }
+
+    /// Jumps a single field of the datetime up or down by a coarse amount.
+    fn jump_field(&self, val: NaiveDateTime, field: DateField, up: bool) -> NaiveDateTime {
+        let amount = match field {
+            DateField::Year => 10,
+            DateField::Month => 3,
+            DateField::Day => 7,
+            DateField::Hour | DateField::Minute | DateField::Second => 10,
+            DateField::Millisecond => 100,
+            DateField::Nanosecond => 1_000_000,
+            DateField::IsoYear => 10,
+            DateField::Week => 4,
+            DateField::Meridiem | DateField::Timezone | DateField::Weekday => 1,
+        };
+        self.shift_field(val, field, if up { amount } else { -amount })
+    }
+
+    /// Sets a single field of the datetime to its lowest or highest valid value.
+    fn bound_field(&self, val: NaiveDateTime, field: DateField, end: bool) -> NaiveDateTime {
+        let pm = if self.hour12 && val.hour() >= 12 { 12 } else { 0 };
+        match field {
+            DateField::Year => {
+                let (low, high) = self.bounds();
+                let year = if end { high.year() } else { low.year() };
+                val.with_year(year)
+                    .or_else(|| val.with_day(28).and_then(|val| val.with_year(year)))
+                    .unwrap_or(val)
+            }
+            DateField::Month => val.with_month(if end { 12 } else { 1 }).unwrap_or(val),
+            DateField::Day => {
+                let day = if end { days_in_month(val.year(), val.month()) } else { 1 };
+                val.with_day(day).unwrap_or(val)
+            }
+            // On a 12-hour clock the hour stays within the current AM/PM half of the day.
+            DateField::Hour if self.hour12 => val.with_hour(pm + if end { 11 } else { 0 }).unwrap_or(val),
+            DateField::Hour => val.with_hour(if end { 23 } else { 0 }).unwrap_or(val),
+            DateField::Minute => val.with_minute(if end { 59 } else { 0 }).unwrap_or(val),
+            DateField::Second => val.with_second(if end { 59 } else { 0 }).unwrap_or(val),
+            DateField::Millisecond => val.with_nanosecond(if end { 999_000_000 } else { 0 }).unwrap_or(val),
+            DateField::Nanosecond => val.with_nanosecond(if end { 999_999_999 } else { 0 }).unwrap_or(val),
+            DateField::Meridiem => val.with_hour(val.hour() % 12 + if end { 12 } else { 0 }).unwrap_or(val),
+            DateField::Timezone => val,
+            DateField::IsoYear => {
+                let (low, high) = self.bounds();
+                let year = if end { high.iso_week().year() } else { low.iso_week().year() };
+                with_iso_year_clamped(val, year).unwrap_or(val)
+            }
+            DateField::Week => {
+                let year = val.iso_week().year();
+                let week = if end { iso_weeks_in_year(year) } else { 1 };
+                with_iso_week(val, year, week, val.weekday().number_from_monday()).unwrap_or(val)
+            }
+            DateField::Weekday => {
+                with_iso_week(val, val.iso_week().year(), val.iso_week().week(), if end { 7 } else { 1 }).unwrap_or(val)
+            }
+        }
+    }
+
+    /// Moves a single field of the datetime by `amount` units, negative amounts move backwards.
+    fn shift_field(&self, val: NaiveDateTime, field: DateField, amount: i64) -> NaiveDateTime {
+        // Wraps a value within `first..first + count` after adding the amount.
+        let wrap = |value: u32, first: u32, count: u32| {
+            ((i64::from(value - first) + amount).rem_euclid(i64::from(count)) as u32) + first
+        };
+        match field {
+            DateField::Year => (0..amount.abs()).fold(val, |val, _| {
+                if amount > 0 {
+                    val.increment_year()
+                } else {
+                    val.decrement_year()
+                }
+            }),
+            DateField::Month if self.wrap_fields => with_month_clamped(val, wrap(val.month(), 1, 12)),
+            DateField::Month => (0..amount.abs()).fold(val, |val, _| {
+                if amount > 0 {
+                    val.increment_month()
+                } else {
+                    val.decrement_month()
+                }
+            }),
+            DateField::Day if self.wrap_fields => {
+                val.with_day(wrap(val.day(), 1, days_in_month(val.year(), val.month()))).unwrap_or(val)
+            }
+            DateField::Day => val + Duration::days(amount),
+            DateField::Hour if self.wrap_fields => val.with_hour(wrap(val.hour(), 0, 24)).unwrap_or(val),
+            DateField::Hour => val + Duration::hours(amount),
+            DateField::Minute if self.wrap_fields => val.with_minute(wrap(val.minute(), 0, 60)).unwrap_or(val),
+            DateField::Minute => val + Duration::minutes(amount),
+            DateField::Second if self.wrap_fields => val.with_second(wrap(val.second(), 0, 60)).unwrap_or(val),
+            DateField::Second => val + Duration::seconds(amount),
+            DateField::Millisecond if self.wrap_fields => {
+                let millis = wrap(val.nanosecond() % 1_000_000_000 / 1_000_000, 0, 1000);
+                val.with_nanosecond(millis * 1_000_000 + val.nanosecond() % 1_000_000).unwrap_or(val)
+            }
+            DateField::Millisecond => val + Duration::milliseconds(amount),
+            DateField::Nanosecond if self.wrap_fields => {
+                val.with_nanosecond(wrap(val.nanosecond() % 1_000_000_000, 0, 1_000_000_000)).unwrap_or(val)
+            }
+            DateField::Nanosecond => val + Duration::nanoseconds(amount),
+            // Toggling AM/PM keeps the date and only moves the hour by 12.
+            DateField::Meridiem => val.with_hour((val.hour() + 12) % 24).unwrap_or(val),
+            // The timezone is not part of the wall clock time.
+            DateField::Timezone => val,
+            DateField::IsoYear => with_iso_year_clamped(val, val.iso_week().year() + amount as i32).unwrap_or(val),
+            DateField::Week if self.wrap_fields => {
+                let (year, weekday) = (val.iso_week().year(), val.weekday().number_from_monday());
+                with_iso_week(val, year, wrap(val.iso_week().week(), 1, iso_weeks_in_year(year)), weekday).unwrap_or(val)
+            }
+            DateField::Week => val + Duration::weeks(amount),
+            DateField::Weekday if self.wrap_fields => {
+                let iso = val.iso_week();
+                with_iso_week(val, iso.year(), iso.week(), wrap(val.weekday().number_from_monday(), 1, 7)).unwrap_or(val)
+            }
+            DateField::Weekday => val + Duration::days(amount),
+        }
+    }
+
+    fn weekday_name(&self, val: NaiveDateTime) -> &'static str {
+        let names = match self.weekday_format {
+            WeekdayFormat::Short => &self.names.short_weekdays,
+            WeekdayFormat::Long => &self.names.weekdays,
+        };
+        names[val.weekday().num_days_from_monday() as usize]
+    }
+
+    /// Returns the months whose name starts with the given prefix, ignoring case.
+    fn months_matching(&self, prefix: &str) -> Vec<u32> {
+        let prefix = prefix.to_lowercase();
+        (1..=12).filter(|&month| self.names.months[month as usize - 1].to_lowercase().starts_with(&prefix)).collect()
+    }
+
+    /// Returns the lowest and highest value that can be typed into a field.
+    fn field_range(&self, val: NaiveDateTime, field: DateField) -> (u32, u32) {
+        match field {
+            DateField::Year => (0, 9999),
+            DateField::Month => (1, 12),
+            DateField::Day => (1, days_in_month(val.year(), val.month())),
+            DateField::Hour if self.hour12 => (1, 12),
+            DateField::Hour => (0, 23),
+            DateField::Minute | DateField::Second => (0, 59),
+            DateField::Millisecond => (0, 999),
+            DateField::Nanosecond => (0, 999_999_999),
+            DateField::Meridiem | DateField::Timezone => (0, 1),
+            DateField::IsoYear => (0, 9999),
+            DateField::Week => (1, iso_weeks_in_year(val.iso_week().year())),
+            DateField::Weekday => (1, 7),
+        }
+    }
+
+    /// Sets a single field from a typed number, using the nearest valid value if it is out of range.
+    fn clamp_field(&self, val: NaiveDateTime, field: DateField, num: u32) -> NaiveDateTime {
+        let (low, high) = self.field_range(val, field);
+        let num = min(max(num, low), high);
+        self.set_field(val, field, num)
+            // Typing a year that has no leap day while on one lands on the 28th.
+            .or_else(|| val.with_day(28).and_then(|val| self.set_field(val, field, num)))
+            .unwrap_or(val)
+    }
+
+    /// Sets a single field from a typed number, returning `None` if it is out of range.
+    fn set_field(&self, val: NaiveDateTime, field: DateField, num: u32) -> Option<NaiveDateTime> {
+        match field {
+            DateField::Year => val.with_year(num as i32),
+            DateField::Month => val.with_month(num),
+            DateField::Day => val.with_day(num),
+            DateField::Hour if self.hour12 => match num {
+                1..=12 => val.with_hour(num % 12 + if val.hour12().0 { 12 } else { 0 }),
+                _ => None,
+            },
+            DateField::Hour => val.with_hour(num),
+            DateField::Minute => val.with_minute(num),
+            DateField::Second => val.with_second(num),
+            DateField::Millisecond if num < 1000 => val.with_nanosecond(num * 1_000_000),
+            DateField::Millisecond => None,
+            DateField::Nanosecond if num < 1_000_000_000 => val.with_nanosecond(num),
+            DateField::Nanosecond => None,
+            DateField::Meridiem | DateField::Timezone => None,
+            DateField::IsoYear => with_iso_week(val, num as i32, val.iso_week().week(), val.weekday().number_from_monday()),
+            DateField::Week => with_iso_week(val, val.iso_week().year(), num, val.weekday().number_from_monday()),
+            DateField::Weekday => with_iso_week(val, val.iso_week().year(), val.iso_week().week(), num),
+        }
+    }
+
+    /// Returns the value the selection starts from and resets to.
+    fn initial_value(&self) -> NaiveDateTime {
+        // Current date in the configured timezone is used as default time if override not set.
+        self.truncate(self.default.unwrap_or_else(|| self.today()))
+    }
+
+    /// Drops the parts of a value that can not be edited, like the time of a Date.
+    ///
+    /// The date of a Time is kept, as it is returned by `interact_datetime`.
+    fn truncate(&self, val: NaiveDateTime) -> NaiveDateTime {
+        if !self.shows_time() {
+            return self.pin_day(val.date().and_hms(0, 0, 0));
+        }
+        let nanos = match self.subsecond {
+            _ if !self.show_seconds => 0,
+            Precision::Seconds => 0,
+            Precision::Millis => val.nanosecond() / 1_000_000 * 1_000_000,
+            Precision::Nanos => val.nanosecond(),
+        };
+        let seconds = if self.show_seconds { val.second() } else { 0 };
+        val.date().and_hms_nano(val.hour(), val.minute(), seconds, nanos)
+    }
+
+    /// Moves a value to the first of its month when the day is not selected.
+    fn pin_day(&self, val: NaiveDateTime) -> NaiveDateTime {
+        match self.date_type {
+            // The first day exists in every month.
+            DateType::YearMonth => val.with_day(1).unwrap(),
+            _ => val,
+        }
+    }
+
+    /// Returns the start of the current day, used as the default and for relative bounds.
+    fn today(&self) -> NaiveDateTime {
+        let now = match self.default_zone {
+            Some(DefaultZone::Utc) => Utc::now().naive_utc(),
+            Some(DefaultZone::Local) => Local::now().naive_local(),
+            None => self.now(),
+        };
+        now.date().and_hms(0, 0, 0)
+    }
+
+    /// Returns the current wall clock time in the configured timezone.
+    fn now(&self) -> NaiveDateTime {
+        match self.timezone {
+            Zone::Utc => Utc::now().naive_utc(),
+            Zone::Local => Local::now().naive_local(),
+            Zone::Fixed(offset) => Utc::now().with_timezone(&offset).naive_local(),
+            #[cfg(feature = "chrono-tz")]
+            Zone::Named(tz) => Utc::now().with_timezone(&tz).naive_local(),
+        }
+    }
+
+    /// Attaches a timezone to a wall clock time.
+    fn localize(&self, val: NaiveDateTime, zone: Zone) -> DateTime<FixedOffset> {
+        let offset = match zone {
+            Zone::Utc => FixedOffset::east(0),
+            Zone::Local => offset_at(&Local, &val),
+            Zone::Fixed(offset) => offset,
+            #[cfg(feature = "chrono-tz")]
+            Zone::Named(tz) => offset_at(&tz, &val),
+        };
+        // A fixed offset maps every wall clock time to exactly one instant.
+        offset.from_local_datetime(&val).unwrap()
+    }
+
+    fn format_result(&self, val: NaiveDateTime, zone: Zone) -> String {
+        if let Some(ref fmt) = self.output_format {
+            return val.format(fmt).to_string();
+        }
+        let (time_format, seconds_format) = match self.subsecond {
+            Precision::Seconds => ("%H:%M:%S", SecondsFormat::Secs),
+            Precision::Millis => ("%H:%M:%S%.3f", SecondsFormat::Millis),
+            Precision::Nanos => ("%H:%M:%S%.9f", SecondsFormat::Nanos),
+        };
+        // A DateTime written without rfc3339 keeps the precision of its seconds_format.
+        let datetime_format = match self.seconds_format {
+            Some(SecondsFormat::Secs) => "%H:%M:%S",
+            Some(SecondsFormat::Millis) => "%H:%M:%S%.3f",
+            Some(SecondsFormat::Micros) => "%H:%M:%S%.6f",
+            Some(SecondsFormat::Nanos) => "%H:%M:%S%.9f",
+            Some(_) => "%H:%M:%S%.f",
+            None => time_format,
+        };
+        // Return the 12-hour clock the user picked the time on, rfc3339 only has 24 hours.
+        let hour12 = |format: &str| format!("{} %p", format.replacen("%H", "%I", 1));
+        match self.date_type {
+            DateType::Date => val.format("%Y-%m-%d").to_string(),
+            DateType::Time if self.hour12 => val.format(&hour12(time_format)).to_string(),
+            DateType::Time => val.format(time_format).to_string(),
+            DateType::YearMonth => val.format("%Y-%m").to_string(),
+            DateType::IsoWeek => val.format("%G-W%V-%u").to_string(),
+            DateType::DateTime if self.hour12 => {
+                let offset = if self.emit_offset { " %:z" } else { "" };
+                self.localize(val, zone).format(&format!("%Y-%m-%d {}{}", hour12(datetime_format), offset)).to_string()
+            }
+            DateType::DateTime if !self.emit_offset => val.format(&format!("%Y-%m-%dT{}", datetime_format)).to_string(),
+            DateType::DateTime => {
+                self.localize(val, zone).to_rfc3339_opts(self.seconds_format.unwrap_or(seconds_format), true)
+            }
+        }
+    }
+
+    /// Styles a value to highlight the field at `pos`, with the weekday if specified.
+    fn format_value(&self, val: NaiveDateTime, zone: Zone, pos: usize, digits: &[u32], plain: bool) -> String {
+        let date_str = self.terminal_format(val, zone, pos, digits, plain);
+        match (self.weekday && self.shows_day(), self.weekday_position) {
+            (true, WeekdayPosition::Prefix) => format!("{}, {}", self.weekday_name(val), date_str),
+            (true, WeekdayPosition::Suffix) => format!("{}, {}", date_str, self.weekday_name(val)),
+            (false, _) => date_str,
+        }
+    }
+
+    /// Styles dashes in place of each field for a selection without a value.
+    fn empty_format(&self, val: NaiveDateTime, zone: Zone, pos: usize, plain: bool) -> String {
+        let mut date_str = String::new();
+        for (idx, (separator, value)) in self.field_values(val, zone).iter().enumerate() {
+            date_str.push_str(separator);
+            let dashes = "-".repeat(measure_text_width(value));
+            write_field(self.theme, &mut date_str, &dashes, idx == pos, plain, self.active_marker);
+        }
+        date_str
+    }
+
+    /// Returns the datetime as it is first shown, without starting the interaction.
+    ///
+    /// The first field is highlighted, with brackets if the terminal of `report_stream` has no colors.
+    pub fn preview(&self) -> String {
+        let state = self.new_state(false, false);
+        self.format_value(state.date_val, state.zone, state.pos, &[], plain_output(&self.term()))
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the terminal of `report_stream`.
+    pub fn interact(&self) -> io::Result<String> {
+        self.interact_on(&self.term())
+    }
+    /// Enables user interaction and returns the result.
+    ///
+    /// None if the user cancelled with Esc.
+    /// The dialog is rendered on the terminal of `report_stream`.
+    pub fn interact_opt(&self) -> io::Result<Option<String>> {
+        self.interact_on_opt(&self.term())
+    }
+    /// Like `interact` but allows a specific terminal to be set.
+    pub fn interact_on(&self, term: &Term) -> io::Result<String> {
+        let selected = self
+            ._interact_on(term, false, false)?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))?;
+        Ok(self.format_result(selected.start, selected.zone))
+    }
+    /// Like `interact_opt` but allows a specific terminal to be set.
+    pub fn interact_on_opt(&self, term: &Term) -> io::Result<Option<String>> {
+        Ok(self
+            ._interact_on(term, true, false)?
+            .map(|selected| self.format_result(selected.start, selected.zone)))
+    }
+    /// Enables user interaction and returns the result, or `None` if it was confirmed empty.
+    ///
+    /// Meant for `allow_empty`, e.g. for an optional end date.
+    /// The dialog is rendered on the terminal of `report_stream`.
+    pub fn interact_optional(&self) -> io::Result<Option<String>> {
+        self.interact_optional_on(&self.term())
+    }
+    /// Like `interact_optional` but allows a specific terminal to be set.
+    pub fn interact_optional_on(&self, term: &Term) -> io::Result<Option<String>> {
+        let selected = self
+            ._interact_on(term, false, false)?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))?;
+        Ok(match selected.empty {
+            true => None,
+            false => Some(self.format_result(selected.start, selected.zone)),
+        })
+    }
+    /// Enables user interaction and returns the selected value as a `NaiveDateTime`.
+    ///
+    /// Unlike `interact` no formatting is applied, so the date portion is kept even for
+    /// `DateType::Time`.
+    /// The dialog is rendered on the terminal of `report_stream`.
+    pub fn interact_datetime(&self) -> io::Result<NaiveDateTime> {
+        self.interact_datetime_on(&self.term())
+    }
+    /// Like `interact_datetime` but allows a specific terminal to be set.
+    pub fn interact_datetime_on(&self, term: &Term) -> io::Result<NaiveDateTime> {
+        self._interact_on(term, false, false)?
+            .map(|selected| selected.start)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+    /// Enables user interaction and returns the selected value as seconds since the Unix epoch.
+    ///
+    /// The dialog is rendered on the terminal of `report_stream`.
+    pub fn interact_timestamp(&self) -> io::Result<i64> {
+        Ok(self.interact_zoned()?.timestamp())
+    }
+    /// Like `interact_timestamp` but returns milliseconds since the Unix epoch.
+    pub fn interact_timestamp_millis(&self) -> io::Result<i64> {
+        Ok(self.interact_zoned()?.timestamp_millis())
+    }
+    /// Enables user interaction and returns the components of the selected value.
+    ///
+    /// The dialog is rendered on the terminal of `report_stream`.
+    pub fn interact_parts(&self) -> io::Result<DateTimeParts> {
+        Ok(self.parts(self.interact_datetime()?))
+    }
+    /// Splits a value into its components.
+    fn parts(&self, val: NaiveDateTime) -> DateTimeParts {
+        DateTimeParts {
+            year: val.year(),
+            month: val.month(),
+            day: val.day(),
+            hour: val.hour(),
+            minute: val.minute(),
+            second: val.second(),
+            nanosecond: val.nanosecond(),
+            seconds_shown: self.fields().contains(&DateField::Second),
+            date_type: self.date_type,
+        }
+    }
+    /// Enables user interaction and returns the selected value as a UTC `DateTime`.
+    ///
+    /// A value selected in another timezone is converted to UTC.
+    /// The dialog is rendered on the terminal of `report_stream`.
+    pub fn interact_utc(&self) -> io::Result<DateTime<Utc>> {
+        Ok(self.interact_zoned()?.with_timezone(&Utc))
+    }
+    /// Runs the selection loop on the `report_stream` terminal, attaching the chosen timezone to the result.
+    fn interact_zoned(&self) -> io::Result<DateTime<FixedOffset>> {
+        let selected = self
+            ._interact_on(&self.term(), false, false)?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))?;
+        Ok(self.localize(selected.start, selected.zone))
+    }
+    /// Enables user interaction to select a start and an end, returning both.
+    ///
+    /// TAB switches between editing the start and the end, the end is kept at or after the
+    /// start. Both are kept within `min` and `max`.
+    /// The dialog is rendered on the terminal of `report_stream`.
+    pub fn interact_range(&self) -> io::Result<(NaiveDateTime, NaiveDateTime)> {
+        self.interact_range_on(&self.term())
+    }
+    /// Like `interact_range` but allows a specific terminal to be set.
+    pub fn interact_range_on(&self, term: &Term) -> io::Result<(NaiveDateTime, NaiveDateTime)> {
+        self._interact_on(term, false, true)?
+            .map(|selected| (selected.start, selected.end))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+    /// Runs the selection loop, returning `None` if quitting is allowed and the user pressed Esc.
+    ///
+    /// With `range` a second datetime is shown after the first and TAB switches which one is edited.
+    fn _interact_on(&self, term: &Term, allow_quit: bool, range: bool) -> io::Result<Option<Selected>> {
+        self.check_range().map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+        let mut state = self.new_state(allow_quit, range);
+        // Without colors the active field is marked with brackets.
+        let plain = plain_output(term);
+        let _cursor = match !self.show_cursor && term.is_term() {
+            true => Some(HiddenCursor::hide(term)?),
+            false => None,
+        };
+        let mut acceleration = Acceleration::default();
+        let mut frame = Frame::default();
+        let mut notified = None;
+
+        loop {
+            self.notify_change(&mut notified, state.date_val);
+
+            // Collect the next frame, it is only written where it differs from the one on screen.
+            let (lines, value_lines) = self.frame_lines(&mut state, plain, term.size().1 as usize);
+
+            frame.draw(term, lines)?;
+
+            let key = match read_key(term) {
+                Ok(key) => key,
+                // Leave no half drawn selection behind when the user presses Ctrl-C.
+                Err(err) => {
+                    if err.kind() == io::ErrorKind::Interrupted {
+                        frame.clear(term, 0)?;
+                    }
+                    return Err(err);
+                }
+            };
+            let repeats = if self.acceleration { acceleration.repeats(&key, Instant::now()) } else { 1 };
+            let repeat_key = repeatable(&key);
+            let mut outcome = self.apply_key(&mut state, key);
+            // Make the extra steps of an accelerated key, stopping early at a bound.
+            for key in repeat_key.iter().cycle().take(repeats - 1).cloned() {
+                if outcome != KeyOutcome::Continue {
+                    break;
+                }
+                outcome = self.apply_key(&mut state, key);
+            }
+            match outcome {
+                KeyOutcome::Continue => {}
+                KeyOutcome::Bell => term.write_str("\x07")?,
+                KeyOutcome::Confirm => {
+                    frame.clear(term, 0)?;
+                    // Without clearing, leave an unstyled line with the confirmed value behind.
+                    if !self.clear {
+                        term.write_line(&self.confirmation_line(&state.selected(), state.range))?;
+                        if self.trailing_newline {
+                            if term.is_term() {
+                                term.write_str("\x1b[0m")?;
+                            }
+                            term.write_line("")?;
+                        }
+                    }
+                    return Ok(Some(state.selected()));
+                }
+                KeyOutcome::Cancel => {
+                    frame.clear(term, if self.clear { 0 } else { value_lines })?;
+                    return Ok(None);
+                }
+            }
+        }
+    }
+
+    /// Returns the lines of the next frame and how many of them show the prompt and datetime.
+    fn frame_lines(&self, state: &mut DateTimeSelectState, plain: bool, term_width: usize) -> (Vec<String>, usize) {
+        // The prompt is either written on its own line or in front of the datetime.
+        let (prompt_line, prompt) = match self.prompt_text() {
+            Some(prompt) if self.prompt_on_own_line => (Some(prompt), None),
+            prompt => (None, prompt),
+        };
+        let format_value = |val, pos| match state.empty {
+            true => self.empty_format(val, state.zone, pos, plain),
+            false => self.format_value(val, state.zone, pos, &state.digits, plain),
+        };
+        // Nothing is edited in read only mode, so no field is highlighted.
+        let pos = if self.read_only { usize::MAX } else { state.pos };
+        let date_str = match (state.range, state.editing_end) {
+            (false, _) => format_value(state.date_val, pos),
+            // The endpoint that is not being edited has no field highlighted.
+            (true, false) => format!(
+                "{} – {}",
+                format_value(state.date_val, pos),
+                format_value(state.other_val, usize::MAX)
+            ),
+            (true, true) => format!(
+                "{} – {}",
+                format_value(state.other_val, usize::MAX),
+                format_value(state.date_val, pos)
+            ),
+        };
+
+        // Writing to a String can not fail.
+        let mut lines = Vec::new();
+        // Keep the datetime on one line in narrow terminals by shortening a long prompt.
+        let prompt = prompt.as_ref().map(|prompt| {
+            let prefix = self.datetime_line(&Some(prompt.clone()), "");
+            // A frame adds a border and a space on both sides.
+            let frame_width = if self.framed { 4 } else { 0 };
+            let decoration = measure_text_width(&prefix).saturating_sub(measure_text_width(prompt)) + frame_width;
+            fit_prompt(prompt, decoration, measure_text_width(&date_str), term_width)
+        });
+        if let Some(prompt_line) = prompt_line {
+            let mut line = String::new();
+            let _ = self.theme.format_prompt(&mut line, &prompt_line);
+            lines.push(line);
+        }
+        let line = self.datetime_line(&prompt, &date_str);
+
+        // Display a caret beneath the field being edited.
+        let mut caret = None;
+        if self.active_marker == Marker::Caret && !self.read_only {
+            let mut column = measure_text_width(&self.datetime_line(&prompt, ""));
+            if state.range && state.editing_end {
+                column += measure_text_width(&format!("{} – ", format_value(state.other_val, usize::MAX)));
+            }
+            if self.weekday && self.shows_day() && self.weekday_position == WeekdayPosition::Prefix {
+                column += measure_text_width(&format!("{}, ", self.weekday_name(state.date_val)));
+            }
+            let (start, width) = self.active_span(state.date_val, state.zone, state.pos, &state.digits);
+            caret = Some(format!("{}{}", " ".repeat(column + start), "^".repeat(width)));
+        }
+
+        if self.framed {
+            let width = measure_text_width(&line);
+            let (mut top, mut framed, mut bottom) = (String::new(), String::new(), String::new());
+            let _ = self.theme.format_datetime_border(&mut top, width, true);
+            let _ = self.theme.format_datetime_framed(&mut framed, &line);
+            let _ = self.theme.format_datetime_border(&mut bottom, width, false);
+            lines.extend(vec![top, framed]);
+            // Keep the caret inside the box, padded to the width of the datetime line.
+            if let Some(caret) = caret.take() {
+                let mut framed = String::new();
+                let _ = self.theme.format_datetime_framed(&mut framed, &format!("{:width$}", caret, width = width));
+                lines.push(framed);
+            }
+            lines.push(bottom);
+        } else {
+            lines.push(line);
+        }
+        // The prompt and datetime stay behind when cancelling without clearing.
+        let value_lines = lines.len();
+        lines.extend(caret);
+
+        // Display the month grid if the calendar is shown.
+        if self.shows_calendar() {
+            lines.extend(self.calendar_lines(state.date_val));
+        }
+
+        // Display typed numbers if show_match is true.
+        if self.show_match {
+            let str_num: Vec<String> = state.digits.iter().map(|c| c.to_string()).collect();
+            lines.push(format!("typing: {}", str_num.join("")));
+        }
+
+        // Display the key bindings if help is toggled on.
+        if state.show_help {
+            lines.push(HELP_TEXT.to_string());
+        }
+
+        if let Some(err) = state.feedback.take() {
+            let line = self.error_line(&prompt, &err);
+            // Line the error up with the datetime inside the frame.
+            lines.push(if self.framed { format!("  {}", line) } else { line });
+        }
+        (lines, value_lines)
+    }
+
+    /// Returns the line with the confirmed value that is left behind without `clear`.
+    fn confirmation_line(&self, selected: &Selected, range: bool) -> String {
+        let mut value = match selected.empty {
+            true => self.empty_format(selected.start, selected.zone, usize::MAX, true),
+            false => self.format_result(selected.start, selected.zone),
+        };
+        if range && !selected.empty {
+            value = format!("{} – {}", value, self.format_result(selected.end, selected.zone));
+        }
+        let mut line = String::new();
+        // Writing to a String can not fail.
+        let _ = match self.prompt {
+            Some(ref prompt) if self.prompt_separator => self.theme.format_single_prompt_selection(&mut line, prompt, &value),
+            Some(ref prompt) => self.theme.format_datetime(&mut line, &None, &format!("{} {}", prompt, value)),
+            None => self.theme.format_datetime(&mut line, &None, &value),
+        };
+        line
+    }
+
+    /// Passes the value to `on_change` if it differs from the value it got last.
+    fn notify_change(&self, notified: &mut Option<NaiveDateTime>, val: NaiveDateTime) {
+        if *notified == Some(val) {
+            return;
+        }
+        *notified = Some(val);
+        if let Some(ref on_change) = self.on_change {
+            on_change(&val);
+        }
+    }
+
+    /// Checks that max is not before min, unless a Time wraps around midnight.
+    fn check_range(&self) -> Result<(), DateTimeError> {
+        if self.max < self.min && self.date_type != DateType::Time {
+            return Err(DateTimeError::InvalidRange);
+        }
+        Ok(())
+    }
+
+    /// Returns the terminal the methods without a terminal render on.
+    fn term(&self) -> Term {
+        match self.report_stream {
+            TermTarget::Stdout => Term::stdout(),
+            TermTarget::Stderr => Term::stderr(),
+        }
+    }
+
+    /// Returns the prompt passed to the theme, including the suffix.
+    fn prompt_text(&self) -> Option<String> {
+        match (&self.prompt, &self.prompt_suffix) {
+            (Some(prompt), Some(suffix)) => Some(format!("{} {}", prompt, suffix)),
+            (prompt, None) => prompt.clone(),
+            (None, suffix) => suffix.clone(),
+        }
+    }
+
+    /// Formats the prompt followed by the datetime, see `prompt_separator`.
+    fn datetime_line(&self, prompt: &Option<String>, datetime: &str) -> String {
+        let mut line = String::new();
+        // Writing to a String can not fail.
+        let _ = match prompt {
+            Some(prompt) if !self.prompt_separator => {
+                self.theme.format_datetime(&mut line, &None, &format!("{} {}", prompt, datetime))
+            }
+            _ => self.theme.format_datetime(&mut line, prompt, datetime),
+        };
+        line
+    }
+
+    /// Formats an error lined up with the datetime of `datetime_line`.
+    fn error_line(&self, prompt: &Option<String>, err: &str) -> String {
+        let mut line = String::new();
+        let _ = match prompt {
+            Some(prompt) if !self.prompt_separator => {
+                line.push_str(&" ".repeat(measure_text_width(prompt) + 1));
+                self.theme.format_datetime_error(&mut line, &None, err)
+            }
+            _ => self.theme.format_datetime_error(&mut line, prompt, err),
+        };
+        line
+    }
+
+    /// Creates the state of a selection to drive it one key at a time with `apply_key`.
+    ///
+    /// Esc cancels such a selection.
+    pub fn state(&self) -> DateTimeSelectState {
+        self.new_state(true, false)
+    }
+    /// Creates the state a selection starts in.
+    fn new_state(&self, allow_quit: bool, range: bool) -> DateTimeSelectState {
+        let date_val = self.pin_day(self.check_date(self.initial_value()));
+        let fields = self.fields();
+        // The calendar starts on the day so the arrow keys move through the grid right away.
+        let pos = match fields.iter().position(|&field| field == DateField::Day) {
+            Some(day) if self.shows_calendar() => day,
+            _ => 0,
+        };
+        DateTimeSelectState {
+            date_val,
+            other_val: date_val,
+            editing_end: false,
+            range,
+            allow_quit,
+            fields,
+            pos,
+            digits: Vec::with_capacity(4),
+            show_help: self.show_help,
+            month_name: String::new(),
+            zone: self.timezone,
+            feedback: None,
+            touched: false,
+            empty: self.allow_empty,
+        }
+    }
+
+    /// Applies a single key press to the state of a selection without touching the terminal.
+    pub fn apply_key(&self, state: &mut DateTimeSelectState, key: Key) -> KeyOutcome {
+        let field = match state.fields.get(state.pos) {
+            Some(&field) => field,
+            None => {
+                // Only reachable through a bug in tracking the position, ignore the key rather than aborting.
+                debug_assert!(false, "position {} out of bounds for {:?}", state.pos, state.fields);
+                state.pos = 0;
+                return KeyOutcome::Continue;
+            }
+        };
+        // A read only value can only be acknowledged.
+        if self.read_only {
+            return match key {
+                Key::Escape => KeyOutcome::Confirm,
+                _ if key == self.confirm_key => KeyOutcome::Confirm,
+                _ => KeyOutcome::Continue,
+            };
+        }
+        let max_pos = state.fields.len() - 1;
+        let before = (state.date_val, state.pos, state.zone, state.editing_end);
+        let mut outcome = KeyOutcome::Continue;
+        let mut typed_month_name = false;
+        // Fewer typed digits than the field needs are used once the field is left or confirmed.
+        let leaves_field = match key {
+            Key::Tab | Key::ArrowLeft | Key::ArrowRight | Key::Char('h') | Key::Char('l') => true,
+            _ => key == self.confirm_key || key == self.advance_key,
+        };
+        if !state.digits.is_empty() && leaves_field {
+            // Fields have at most 9 digits, so the number fits.
+            let num = typed_value(&state.digits) as u32;
+            let num = match (field, state.digits.len()) {
+                (DateField::Year, 1) | (DateField::Year, 2) if num < self.year_pivot => 2000 + num,
+                (DateField::Year, 1) | (DateField::Year, 2) => 1900 + num,
+                _ => num,
+            };
+            state.date_val = self.check_field(self.clamp_field(state.date_val, field, num), field);
+            state.digits.clear();
+        }
+        match key {
+            // An empty selection has no value to check.
+            _ if key == self.confirm_key && state.empty => return KeyOutcome::Confirm,
+            // Refuse to confirm a disabled date.
+            _ if key == self.confirm_key
+                && (self.is_disabled(state.date_val) || (state.range && self.is_disabled(state.other_val))) =>
+            {
+                state.feedback = Some("This date is not available".into());
+                outcome = KeyOutcome::Bell;
+            }
+            // Refuse to confirm the untouched default when a selection has to be made.
+            _ if key == self.confirm_key && self.require_interaction && !state.touched => {
+                state.feedback = Some("Please make a selection first".into());
+                outcome = KeyOutcome::Bell;
+            }
+            _ if key == self.confirm_key => {
+                if !self.show_seconds && self.date_type != DateType::Date {
+                    state.date_val = state.date_val.date().and_hms(state.date_val.hour(), state.date_val.minute(), 0);
+                    state.other_val = state.other_val.date().and_hms(state.other_val.hour(), state.other_val.minute(), 0);
+                }
+                let selected = state.selected();
+                // Keep the selection open and show the error if the validator rejects a value.
+                let err = match self.validator {
+                    Some(ref validator) => {
+                        validator(&selected.start).or_else(|| if state.range { validator(&selected.end) } else { None })
+                    }
+                    None => None,
+                };
+                if err.is_some() {
+                    state.feedback = err;
+                    outcome = KeyOutcome::Bell;
+                } else {
+                    return KeyOutcome::Confirm;
+                }
+            }
+            // Letters on the month field select the first month whose name starts with them. Other
+            // bindings like h/j/k/l only apply there when no month name starts with the letter.
+            Key::Char(val)
+                if field == DateField::Month && !self.months_matching(&format!("{}{}", state.month_name, val)).is_empty() =>
+            {
+                state.month_name.push(val);
+                let months = self.months_matching(&state.month_name);
+                state.date_val = with_month_clamped(state.date_val, months[0]);
+                // Keep collecting letters until the name is unambiguous.
+                typed_month_name = months.len() > 1;
+                state.digits.clear();
+            }
+            // Jump back to the current date, kept within min/max below.
+            _ if key == self.today_key => {
+                state.date_val = self.truncate(self.today());
+                state.digits.clear();
+            }
+            Key::Escape if state.allow_quit => return KeyOutcome::Cancel,
+            Key::Char('?') => {
+                state.show_help = !state.show_help;
+            }
+            // Move through the calendar grid by days and weeks.
+            Key::ArrowLeft | Key::ArrowRight | Key::ArrowUp | Key::ArrowDown
+                if self.shows_calendar() && field == DateField::Day =>
+            {
+                let days = match key {
+                    Key::ArrowLeft => Duration::days(-1),
+                    Key::ArrowRight => Duration::days(1),
+                    Key::ArrowUp => Duration::days(-7),
+                    _ => Duration::days(7),
+                };
+                outcome = self.step_bounded(state, DateField::Day, |val| val + days);
+            }
+            // Move on without the arrow keys, also between the fields of a range endpoint.
+            _ if key == self.advance_key => {
+                state.pos = if state.pos == max_pos { 0 } else { state.pos + 1 };
+                state.digits.clear();
+            }
+            // Switch between editing the start and the end of a range.
+            Key::Tab if state.range => {
+                std::mem::swap(&mut state.date_val, &mut state.other_val);
+                state.editing_end = !state.editing_end;
+                state.digits.clear();
+            }
+            Key::ArrowRight | Key::Tab | Key::Char('l') => {
+                state.pos = if state.pos == max_pos { 0 } else { state.pos + 1 };
+                state.digits.clear();
+            }
+            Key::ArrowLeft | Key::Char('h') => {
+                state.pos = if state.pos == 0 { max_pos } else { state.pos - 1 };
+                state.digits.clear();
+            }
+            // Backspace removes the last typed digit, or decrements the field when nothing was typed.
+            Key::Backspace if !state.digits.is_empty() => {
+                state.digits.pop();
+            }
+            // Cycle through the named timezones.
+            #[cfg(feature = "chrono-tz")]
+            Key::ArrowUp | Key::Char('k') | Key::ArrowDown | Key::Char('j') | Key::Backspace if field == DateField::Timezone => {
+                state.zone = state.zone.cycle(key == Key::ArrowUp || key == Key::Char('k'));
+                state.digits.clear();
+            }
+            // Increment datetime by 1.
+            Key::ArrowUp | Key::Char('k') => {
+                outcome = self.step_bounded(state, field, |val| self.step_field(val, field, true));
+            }
+            // Decrement the datetime by 1.
+            Key::ArrowDown | Key::Char('j') | Key::Backspace => {
+                outcome = self.step_bounded(state, field, |val| self.step_field(val, field, false));
+            }
+            // Jump the datetime by a coarse amount.
+            Key::Char('K') => {
+                outcome = self.step_bounded(state, field, |val| self.jump_field(val, field, true));
+            }
+            Key::Char('J') => {
+                outcome = self.step_bounded(state, field, |val| self.jump_field(val, field, false));
+            }
+            // Jump the focused field to its lowest or highest value.
+            Key::Home | Key::Del => {
+                state.date_val = self.bound_field(state.date_val, field, false);
+                state.digits.clear();
+            }
+            Key::End => {
+                state.date_val = self.bound_field(state.date_val, field, true);
+                state.digits.clear();
+            }
+            // Snap the whole value to the earliest or latest allowed instant.
+            Key::Char('[') => {
+                state.date_val = self.bounds().0;
+                state.digits.clear();
+            }
+            Key::Char(']') => {
+                state.date_val = self.bounds().1;
+                state.digits.clear();
+            }
+            // Reset to the starting value, discarding all edits.
+            Key::Char('r') => {
+                state.date_val = self.initial_value();
+                state.digits.clear();
+            }
+            // Months and days start at 1, so a second leading zero is ignored rather than rejected.
+            Key::Char('0')
+                if self.allow_typing && (field == DateField::Month || field == DateField::Day) && state.digits == [0] => {}
+            // Allow numerical inputs, unless only the arrow keys should change the value.
+            Key::Char(val) if self.allow_typing => {
+                if let Some(digit) = val.to_digit(10) {
+                    state.digits.push(digit);
+                    let num = typed_value(&state.digits) as u32;
+                    // Any day of a month can be typed, days past the end of this month are clamped below.
+                    let highest = match field {
+                        DateField::Day => 31,
+                        _ => self.field_range(state.date_val, field).1,
+                    };
+                    // Set the number early when another digit would go past the field's largest value.
+                    if digits_complete(&state.digits, digits_needed(field), highest) {
+                        let last_day = days_in_month(state.date_val.year(), state.date_val.month());
+                        state.date_val = match self.set_field(state.date_val, field, num) {
+                            Some(val) => val,
+                            None if field == DateField::Day && num > last_day && num <= 31 => {
+                                state.feedback = Some(format!("This month only has {} days", last_day));
+                                self.clamp_field(state.date_val, field, num)
+                            }
+                            None => match self.on_invalid_entry {
+                                InvalidEntry::Ignore => state.date_val,
+                                InvalidEntry::Bell => {
+                                    outcome = KeyOutcome::Bell;
+                                    state.date_val
+                                }
+                                InvalidEntry::Clamp => self.clamp_field(state.date_val, field, num),
+                            },
+                        };
+                        state.digits.clear();
+                    }
+                } else {
+                    state.digits.clear();
+                }
+            }
+            _ => {}
+        }
+        if !typed_month_name {
+            state.month_name.clear();
+        }
+        state.date_val = self.pin_day(self.check_field(state.date_val, state.fields.get(state.pos).cloned().unwrap_or(field)));
+        // Keep the end of a range at or after its start.
+        if state.editing_end {
+            state.date_val = max(state.date_val, state.other_val);
+        } else {
+            state.other_val = max(state.other_val, state.date_val);
+        }
+        if (state.date_val, state.pos, state.zone, state.editing_end) != before || !state.digits.is_empty() {
+            state.touched = true;
+        }
+        // The first edit enters a value, moving between the fields does not.
+        if state.date_val != before.0 || !state.digits.is_empty() {
+            state.empty = false;
+        }
+        outcome
+    }
+}
+
+/// The fields of a `DurationSelect`, in the order they are shown.
+const DURATION_FIELDS: [DateField; 3] = [DateField::Hour, DateField::Minute, DateField::Second];
+
+/// Renders a duration selection interactive text.
+///
+/// Hours, minutes and seconds are edited like the fields of a `DateTimeSelect`, but the value
+/// is a length of time rather than a clock time, so hours are not capped at 23.
+///
+/// Note: Fields can be changed by UP/DOWN/k/j or specifying numerical values and are selected
+/// with LEFT/RIGHT/h/l or TAB. Enter confirms the duration, including digits that are still
+/// being typed, see confirm_key.
+/// Ctrl-C clears the selection and returns an error of kind `io::ErrorKind::Interrupted`.
+#[derive(Clone)]
+pub struct DurationSelect<'a> {
+    prompt: Option<String>,
+    default: Duration,
+    theme: &'a dyn Theme,
+    min: Duration,
+    max: Option<Duration>,
+    clear: bool,
+    confirm_key: Key,
+    report_stream: TermTarget,
+}
+
+/// Same as `DurationSelect::new()`, which `DurationSelect::default()` can not reach as the `default` setter shadows it.
+impl<'a> Default for DurationSelect<'a> {
+    fn default() -> DurationSelect<'a> {
+        DurationSelect::new()
+    }
+}
+
+impl<'a> DurationSelect<'a> {
+    pub fn new() -> DurationSelect<'static> {
+        DurationSelect::with_theme(get_default_theme())
+    }
+
+    /// Creates a duration selection with a specific theme.
+    pub fn with_theme(theme: &'a dyn Theme) -> DurationSelect<'a> {
+        DurationSelect {
+            prompt: None,
+            default: Duration::zero(),
+            theme,
+            min: Duration::zero(),
+            max: None,
+            clear: true,
+            confirm_key: Key::Enter,
+            report_stream: TermTarget::Stderr,
+        }
+    }
+    /// Sets the duration prompt.
+    pub fn with_prompt(&mut self, prompt: &str) -> &mut Self {
+        self.prompt = Some(prompt.into());
+        self
+    }
+    /// Sets the starting duration, defaults to zero.
+    pub fn default(&mut self, val: Duration) -> &mut Self {
+        self.default = val;
+        self
+    }
+    /// Sets the shortest duration that can be selected, defaults to zero.
+    ///
+    /// Interacting returns an error if it is longer than `max`.
+    pub fn min(&mut self, val: Duration) -> &mut Self {
+        self.min = val;
+        self
+    }
+    /// Sets the longest duration that can be selected, unlimited by default.
+    pub fn max(&mut self, val: Duration) -> &mut Self {
+        self.max = Some(val);
+        self
+    }
+    /// Sets whether to clear inputs from terminal.
+    ///
+    /// When not cleared the line with the confirmed duration is left behind.
+    pub fn clear(&mut self, val: bool) -> &mut Self {
+        self.clear = val;
+        self
+    }
+    /// Sets the key that confirms the duration, defaults to Enter.
+    ///
+    /// Enter has no effect when another key is set.
+    pub fn confirm_key(&mut self, val: Key) -> &mut Self {
+        self.confirm_key = val;
+        self
+    }
+    /// Sets whether `interact` and `interact_opt` render on stdout or stderr.
+    ///
+    /// Defaults to stderr.
+    pub fn report_stream(&mut self, val: TermTarget) -> &mut Self {
+        self.report_stream = val;
+        self
+    }
+
+    /// Keeps a number of seconds within `min` and `max`.
+    fn check_seconds(&self, total: i64) -> i64 {
+        let total = max(total, self.min.num_seconds());
+        match self.max {
+            Some(val) => min(total, val.num_seconds()),
+            None => total,
+        }
+    }
+
+    /// Returns how many digits set a field and the highest value that can be typed into it.
+    fn field_digits(&self, field: DateField) -> (usize, u32) {
+        match field {
+            // Hours need as many digits as the longest duration has, but at least 2.
+            DateField::Hour => match self.max {
+                Some(val) => (max(2, val.num_hours().to_string().len()), val.num_hours() as u32),
+                None => (2, u32::MAX),
+            },
+            _ => (2, 59),
+        }
+    }
+
+    fn terminal_format(&self, total: i64, pos: usize, digits: &[u32], plain: bool) -> String {
+        let values = [total / 3600, total % 3600 / 60, total % 60];
+        let mut duration_str = String::new();
+        for (idx, value) in values.iter().enumerate() {
+            if idx > 0 {
+                duration_str.push(':');
+            }
+            let value = match (idx == pos, digits.is_empty()) {
+                // Digits being typed are padded with `_` up to the digits the field needs.
+                (true, false) => {
+                    let typed: String = digits.iter().map(|digit| digit.to_string()).collect();
+                    format!("{:_<width$}", typed, width = self.field_digits(DURATION_FIELDS[idx]).0)
+                }
+                _ => format!("{:02}", value),
+            };
+            write_field(self.theme, &mut duration_str, &value, idx == pos, plain, Marker::Style);
+        }
+        duration_str
+    }
+
+    /// Formats the prompt followed by the duration.
+    fn line(&self, total: i64, pos: usize, digits: &[u32], plain: bool) -> String {
+        let mut line = String::new();
+        // Writing to a String can not fail.
+        let _ = self.theme.format_datetime(&mut line, &self.prompt, &self.terminal_format(total, pos, digits, plain));
+        line
+    }
+
+    /// Replaces the hours, minutes or seconds of a number of seconds, returning `None` if out of range.
+    fn set_field(&self, total: i64, field: DateField, num: u32) -> Option<i64> {
+        let num = i64::from(num);
+        match field {
+            DateField::Hour => Some(num * 3600 + total % 3600),
+            DateField::Minute if num < 60 => Some(total - total % 3600 + num * 60 + total % 60),
+            DateField::Second if num < 60 => Some(total - total % 60 + num),
+            _ => None,
+        }
+    }
+
+    /// Sets the field at `pos` to the typed digits, kept within `min` and `max`.
+    fn commit_digits(&self, total: &mut i64, pos: usize, digits: &mut Vec<u32>) {
+        if !digits.is_empty() {
+            let typed = min(typed_value(digits), u64::from(u32::MAX)) as u32;
+            *total = self.check_seconds(self.set_field(*total, DURATION_FIELDS[pos], typed).unwrap_or(*total));
+            digits.clear();
+        }
+    }
+
+    /// Applies a key to the duration in seconds, the field at `pos` and the digits typed so far,
+    /// like `DateTimeSelect::apply_key`.
+    fn apply_key(&self, total: &mut i64, pos: &mut usize, digits: &mut Vec<u32>, key: Key, allow_quit: bool) -> KeyOutcome {
+        let max_pos = DURATION_FIELDS.len() - 1;
+        let step = [3600, 60, 1][*pos];
+        let step = match key {
+            Key::ArrowUp | Key::Char('k') => step,
+            Key::ArrowDown | Key::Char('j') => -step,
+            _ => 0,
+        };
+        match key {
+            _ if key == self.confirm_key => {
+                self.commit_digits(total, *pos, digits);
+                return KeyOutcome::Confirm;
+            }
+            Key::Escape if allow_quit => return KeyOutcome::Cancel,
+            Key::ArrowRight | Key::Tab | Key::Char('l') => {
+                *pos = if *pos == max_pos { 0 } else { *pos + 1 };
+                digits.clear();
+            }
+            Key::ArrowLeft | Key::Char('h') => {
+                *pos = if *pos == 0 { max_pos } else { *pos - 1 };
+                digits.clear();
+            }
+            // Stepping stops at min and max, a step that can not move at all is refused.
+            _ if step != 0 => {
+                digits.clear();
+                let stepped = self.check_seconds(*total + step);
+                if stepped == *total {
+                    return KeyOutcome::Bell;
+                }
+                *total = stepped;
+            }
+            Key::Backspace => {
+                digits.pop();
+            }
+            Key::Char(val) => match val.to_digit(10) {
+                Some(digit) => {
+                    digits.push(digit);
+                    let (needed, highest) = self.field_digits(DURATION_FIELDS[*pos]);
+                    if digits_complete(digits, needed, highest) {
+                        self.commit_digits(total, *pos, digits);
+                    }
+                }
+                None => digits.clear(),
+            },
+            _ => {}
+        }
+        KeyOutcome::Continue
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the terminal of `report_stream`.
+    pub fn interact(&self) -> io::Result<Duration> {
+        self.interact_on(&self.term())
+    }
+    /// Enables user interaction and returns the result.
+    ///
+    /// None if the user cancelled with Esc.
+    /// The dialog is rendered on the terminal of `report_stream`.
+    pub fn interact_opt(&self) -> io::Result<Option<Duration>> {
+        self._interact_on(&self.term(), true)
+    }
+    /// Like `interact` but allows a specific terminal to be set.
+    pub fn interact_on(&self, term: &Term) -> io::Result<Duration> {
+        self._interact_on(term, false)?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+    /// Like `interact_opt` but allows a specific terminal to be set.
+    pub fn interact_on_opt(&self, term: &Term) -> io::Result<Option<Duration>> {
+        self._interact_on(term, true)
+    }
+    fn _interact_on(&self, term: &Term, allow_quit: bool) -> io::Result<Option<Duration>> {
+        if self.max.filter(|&val| val < self.min).is_some() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, DateTimeError::InvalidRange));
+        }
+        let mut total = self.check_seconds(self.default.num_seconds());
+        let mut pos = 0;
+        let mut digits: Vec<u32> = Vec::with_capacity(2);
+        // Without colors the active field is marked with brackets.
+        let plain = plain_output(term);
+        let _cursor = match term.is_term() {
+            true => Some(HiddenCursor::hide(term)?),
+            false => None,
+        };
+        let mut frame = Frame::default();
+
+        loop {
+            frame.draw(term, vec![self.line(total, pos, &digits, plain)])?;
+
+            let key = match read_key(term) {
+                Ok(key) => key,
+                // Leave no half drawn selection behind when the user presses Ctrl-C.
+                Err(err) => {
+                    if err.kind() == io::ErrorKind::Interrupted {
+                        frame.clear(term, 0)?;
+                    }
+                    return Err(err);
+                }
+            };
+            match self.apply_key(&mut total, &mut pos, &mut digits, key, allow_quit) {
+                KeyOutcome::Continue => {}
+                KeyOutcome::Bell => term.write_str("\x07")?,
+                outcome => {
+                    if self.clear {
+                        frame.clear(term, 0)?;
+                    } else {
+                        // Show the duration digits still being typed were set to.
+                        frame.draw(term, vec![self.line(total, pos, &digits, plain)])?;
+                    }
+                    return Ok(match outcome {
+                        KeyOutcome::Confirm => Some(Duration::seconds(total)),
+                        _ => None,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Returns the terminal `interact` and `interact_opt` render on.
+    fn term(&self) -> Term {
+        match self.report_stream {
+            TermTarget::Stdout => Term::stdout(),
+            TermTarget::Stderr => Term::stderr(),
+        }
+    }
+}
+
+/// Renders a quarter selection interactive text, like `Q3 2024`.
+///
+/// The result is the first day of the quarter, or the year and quarter with `interact_quarter`.
+///
+/// Note: The quarter and year can be changed by UP/DOWN/k/j or specifying numerical values and
+/// are selected with LEFT/RIGHT/h/l or TAB. Stepping the quarter past Q4 moves on to the next year.
+/// Enter confirms the quarter, including digits that are still being typed, see confirm_key.
+/// Ctrl-C clears the selection and returns an error of kind `io::ErrorKind::Interrupted`.
+#[derive(Clone)]
+pub struct QuarterSelect<'a> {
+    prompt: Option<String>,
+    default: Option<NaiveDate>,
+    default_zone: DefaultZone,
+    theme: &'a dyn Theme,
+    clear: bool,
+    confirm_key: Key,
+    report_stream: TermTarget,
+}
+
+/// Same as `QuarterSelect::new()`, which `QuarterSelect::default()` can not reach as the `default` setter shadows it.
+impl<'a> Default for QuarterSelect<'a> {
+    fn default() -> QuarterSelect<'a> {
+        QuarterSelect::new()
+    }
+}
+
+impl<'a> QuarterSelect<'a> {
+    pub fn new() -> QuarterSelect<'static> {
+        QuarterSelect::with_theme(get_default_theme())
+    }
+
+    /// Creates a quarter selection with a specific theme.
+    pub fn with_theme(theme: &'a dyn Theme) -> QuarterSelect<'a> {
+        QuarterSelect {
+            prompt: None,
+            default: None,
+            default_zone: DefaultZone::Utc,
+            theme,
+            clear: true,
+            confirm_key: Key::Enter,
+            report_stream: TermTarget::Stderr,
+        }
+    }
+    /// Sets the quarter prompt.
+    pub fn with_prompt(&mut self, prompt: &str) -> &mut Self {
+        self.prompt = Some(prompt.into());
+        self
+    }
+    /// Starts from the quarter containing the given date, defaults to the current quarter.
+    pub fn default(&mut self, val: NaiveDate) -> &mut Self {
+        self.default = Some(val);
+        self
+    }
+    /// Sets the clock the current quarter comes from when no default is set, defaults to UTC.
+    pub fn default_zone(&mut self, val: DefaultZone) -> &mut Self {
+        self.default_zone = val;
+        self
+    }
+    /// Sets whether to clear inputs from terminal.
+    ///
+    /// When not cleared the line with the confirmed quarter is left behind.
+    pub fn clear(&mut self, val: bool) -> &mut Self {
+        self.clear = val;
+        self
+    }
+    /// Sets the key that confirms the quarter, defaults to Enter.
+    ///
+    /// Enter has no effect when another key is set.
+    pub fn confirm_key(&mut self, val: Key) -> &mut Self {
+        self.confirm_key = val;
+        self
+    }
+    /// Sets whether `interact` and `interact_opt` render on stdout or stderr.
+    ///
+    /// Defaults to stderr.
+    pub fn report_stream(&mut self, val: TermTarget) -> &mut Self {
+        self.report_stream = val;
+        self
+    }
+
+    /// Returns the current date on the clock of `default_zone`.
+    fn today(&self) -> NaiveDate {
+        match self.default_zone {
+            DefaultZone::Utc => Utc::now().naive_utc().date(),
+            DefaultZone::Local => Local::now().naive_local().date(),
+        }
+    }
+
+    /// Returns the year and quarter of a count of quarters since year 0.
+    fn split(quarters: i32) -> (i32, u32) {
+        (quarters.div_euclid(4), quarters.rem_euclid(4) as u32 + 1)
+    }
+
+    fn terminal_format(&self, quarters: i32, pos: usize, digits: &[u32], plain: bool) -> String {
+        let (year, quarter) = QuarterSelect::split(quarters);
+        let values = [quarter.to_string(), year.to_string()];
+        let mut quarter_str = String::from("Q");
+        for (idx, value) in values.iter().enumerate() {
+            if idx > 0 {
+                quarter_str.push(' ');
+            }
+            let value = match (idx == pos, digits.is_empty()) {
+                // Digits being typed are padded with `_` up to the digits the field needs.
+                (true, false) => {
+                    let typed: String = digits.iter().map(|digit| digit.to_string()).collect();
+                    format!("{:_<width$}", typed, width = QUARTER_DIGITS[idx])
+                }
+                _ => value.clone(),
+            };
+            write_field(self.theme, &mut quarter_str, &value, idx == pos, plain, Marker::Style);
+        }
+        quarter_str
+    }
+
+    /// Formats the prompt followed by the quarter.
+    fn line(&self, quarters: i32, pos: usize, digits: &[u32], plain: bool) -> String {
+        let mut line = String::new();
+        // Writing to a String can not fail.
+        let _ = self.theme.format_datetime(&mut line, &self.prompt, &self.terminal_format(quarters, pos, digits, plain));
+        line
+    }
+
+    /// Sets the quarter or year at `pos` to the typed digits, a quarter outside 1 to 4 is ignored.
+    fn commit_digits(&self, quarters: &mut i32, pos: usize, digits: &mut Vec<u32>) {
+        if digits.is_empty() {
+            return;
+        }
+        let num = typed_value(digits) as i32;
+        let (year, quarter) = QuarterSelect::split(*quarters);
+        match pos {
+            0 if (1..=4).contains(&num) => *quarters = year * 4 + num - 1,
+            0 => {}
+            _ => *quarters = num * 4 + quarter as i32 - 1,
+        }
+        digits.clear();
+    }
+
+    /// Applies a key to the count of quarters, the field at `pos` and the digits typed so far,
+    /// like `DateTimeSelect::apply_key`.
+    fn apply_key(&self, quarters: &mut i32, pos: &mut usize, digits: &mut Vec<u32>, key: Key, allow_quit: bool) -> KeyOutcome {
+        let step = if *pos == 0 { 1 } else { 4 };
+        match key {
+            _ if key == self.confirm_key => {
+                self.commit_digits(quarters, *pos, digits);
+                return KeyOutcome::Confirm;
+            }
+            Key::Escape if allow_quit => return KeyOutcome::Cancel,
+            Key::ArrowRight | Key::ArrowLeft | Key::Tab | Key::Char('l') | Key::Char('h') => {
+                *pos = 1 - *pos;
+                digits.clear();
+            }
+            Key::ArrowUp | Key::Char('k') => {
+                *quarters += step;
+                digits.clear();
+            }
+            Key::ArrowDown | Key::Char('j') => {
+                *quarters -= step;
+                digits.clear();
+            }
+            Key::Backspace => {
+                digits.pop();
+            }
+            Key::Char(val) => match val.to_digit(10) {
+                Some(digit) => {
+                    digits.push(digit);
+                    if digits_complete(digits, QUARTER_DIGITS[*pos], [4, 9999][*pos]) {
+                        self.commit_digits(quarters, *pos, digits);
+                    }
+                }
+                None => digits.clear(),
+            },
+            _ => {}
+        }
+        KeyOutcome::Continue
+    }
+
+    /// Enables user interaction and returns the first day of the selected quarter.
+    ///
+    /// The dialog is rendered on the terminal of `report_stream`.
+    pub fn interact(&self) -> io::Result<NaiveDate> {
+        self.interact_on(&self.term())
+    }
+    /// Enables user interaction and returns the first day of the selected quarter.
+    ///
+    /// None if the user cancelled with Esc.
+    /// The dialog is rendered on the terminal of `report_stream`.
+    pub fn interact_opt(&self) -> io::Result<Option<NaiveDate>> {
+        self.interact_on_opt(&self.term())
+    }
+    /// Like `interact` but allows a specific terminal to be set.
+    pub fn interact_on(&self, term: &Term) -> io::Result<NaiveDate> {
+        let (year, quarter) = self.interact_quarter_on(term)?;
+        Ok(first_day_of_quarter(year, quarter))
+    }
+    /// Like `interact_opt` but allows a specific terminal to be set.
+    pub fn interact_on_opt(&self, term: &Term) -> io::Result<Option<NaiveDate>> {
+        Ok(self._interact_on(term, true)?.map(|(year, quarter)| first_day_of_quarter(year, quarter)))
+    }
+    /// Enables user interaction and returns the selected year and quarter, from 1 to 4.
+    ///
+    /// The dialog is rendered on the terminal of `report_stream`.
+    pub fn interact_quarter(&self) -> io::Result<(i32, u32)> {
+        self.interact_quarter_on(&self.term())
+    }
+    /// Like `interact_quarter` but allows a specific terminal to be set.
+    pub fn interact_quarter_on(&self, term: &Term) -> io::Result<(i32, u32)> {
+        self._interact_on(term, false)?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+    fn _interact_on(&self, term: &Term, allow_quit: bool) -> io::Result<Option<(i32, u32)>> {
+        let start = self.default.unwrap_or_else(|| self.today());
+        // Counting quarters since year 0 lets stepping carry over into the year.
+        let mut quarters = start.year() * 4 + start.month0() as i32 / 3;
+        let mut pos = 0;
+        let mut digits: Vec<u32> = Vec::with_capacity(4);
+        // Without colors the active field is marked with brackets.
+        let plain = plain_output(term);
+        let _cursor = match term.is_term() {
+            true => Some(HiddenCursor::hide(term)?),
+            false => None,
+        };
+        let mut frame = Frame::default();
+
+        loop {
+            frame.draw(term, vec![self.line(quarters, pos, &digits, plain)])?;
+
+            let key = match read_key(term) {
+                Ok(key) => key,
+                // Leave no half drawn selection behind when the user presses Ctrl-C.
+                Err(err) => {
+                    if err.kind() == io::ErrorKind::Interrupted {
+                        frame.clear(term, 0)?;
+                    }
+                    return Err(err);
                 }
-                // Increment datetime by 1.
-                Key::ArrowUp | Key::Char('j') => {
-                    date_val = match (self.date_type, pos) {
-                        (DateType::DateTime, 0) | (DateType::Date, 0) => date_val.increment_year(),
-                        (DateType::DateTime, 1) | (DateType::Date, 1) => date_val.increment_month(),
-                        (DateType::DateTime, 2) | (DateType::Date, 2) => date_val + Duration::days(1),
-                        (DateType::DateTime, 3) | (DateType::Time, 0) => date_val + Duration::hours(1),
-                        (DateType::DateTime, 4) | (DateType::Time, 1) => date_val + Duration::minutes(1),
-                        (DateType::DateTime, 5) | (DateType::Time, 2) => date_val + Duration::seconds(1),
-                        (DateType::Date, _) => panic!("stepped out of bounds on Date"),
-                        (DateType::Time, _) => panic!("stepped out of bounds on Time"),
-                        (DateType::DateTime, _) => panic!("stepped out of bounds on DateTime"),
-                    };
+            };
+            match self.apply_key(&mut quarters, &mut pos, &mut digits, key, allow_quit) {
+                KeyOutcome::Continue | KeyOutcome::Bell => {}
+                outcome => {
+                    if self.clear {
+                        frame.clear(term, 0)?;
+                    } else {
+                        // Show the quarter digits still being typed were set to.
+                        frame.draw(term, vec![self.line(quarters, pos, &digits, plain)])?;
+                    }
+                    return Ok(match outcome {
+                        KeyOutcome::Confirm => Some(QuarterSelect::split(quarters)),
+                        _ => None,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Returns the terminal `interact` and `interact_opt` render on.
+    fn term(&self) -> Term {
+        match self.report_stream {
+            TermTarget::Stdout => Term::stdout(),
+            TermTarget::Stderr => Term::stderr(),
+        }
+    }
+}
+
+/// The digits that set the quarter and the year of a `QuarterSelect`.
+const QUARTER_DIGITS: [usize; 2] = [1, 4];
+
+/// Returns the first day of a quarter, from 1 to 4.
+fn first_day_of_quarter(year: i32, quarter: u32) -> NaiveDate {
+    NaiveDate::from_ymd(year, (quarter - 1) * 3 + 1, 1)
+}
+
+/// Renders a single number to pick, like a number of minutes.
+///
+/// Note: The number can be changed by UP/DOWN/k/j or typed, it is set once as many digits as
+/// `max` has are typed, or earlier when more digits would go past `max`. Enter confirms the
+/// number, including digits that are still being typed. HOME/END select `min` and `max`.
+/// Ctrl-C clears the selection and returns an error of kind `io::ErrorKind::Interrupted`.
+#[derive(Clone)]
+pub struct NumberSelect<'a> {
+    prompt: Option<String>,
+    default: u32,
+    theme: &'a dyn Theme,
+    min: u32,
+    max: u32,
+    step: u32,
+    clear: bool,
+    report_stream: TermTarget,
+}
+
+/// Same as `NumberSelect::new()`, which `NumberSelect::default()` can not reach as the `default` setter shadows it.
+impl<'a> Default for NumberSelect<'a> {
+    fn default() -> NumberSelect<'a> {
+        NumberSelect::new()
+    }
+}
+
+impl<'a> NumberSelect<'a> {
+    /// Creates a number selection from 0 to 59 with the default theme.
+    pub fn new() -> NumberSelect<'static> {
+        NumberSelect::with_theme(get_default_theme())
+    }
+
+    /// Creates a number selection with a specific theme.
+    pub fn with_theme(theme: &'a dyn Theme) -> NumberSelect<'a> {
+        NumberSelect {
+            prompt: None,
+            default: 0,
+            theme,
+            min: 0,
+            max: 59,
+            step: 1,
+            clear: true,
+            report_stream: TermTarget::Stderr,
+        }
+    }
+    /// Sets the number prompt.
+    pub fn with_prompt(&mut self, prompt: &str) -> &mut Self {
+        self.prompt = Some(prompt.into());
+        self
+    }
+    /// Sets the starting number, defaults to zero.
+    pub fn default(&mut self, val: u32) -> &mut Self {
+        self.default = val;
+        self
+    }
+    /// Sets the smallest number that can be selected, defaults to zero.
+    ///
+    /// Interacting returns an error if it is larger than `max`.
+    pub fn min(&mut self, val: u32) -> &mut Self {
+        self.min = val;
+        self
+    }
+    /// Sets the largest number that can be selected, defaults to 59 as for minutes.
+    pub fn max(&mut self, val: u32) -> &mut Self {
+        self.max = val;
+        self
+    }
+    /// Sets how far UP/DOWN move the number, defaults to 1.
+    ///
+    /// Panics on a step of zero, see `try_step` for a fallible version.
+    pub fn step(&mut self, val: u32) -> &mut Self {
+        self.try_step(val).unwrap_or_else(|err| panic!("{}", err))
+    }
+    /// Like `step` but returns an error instead of panicking on a step of zero.
+    pub fn try_step(&mut self, val: u32) -> Result<&mut Self, DateTimeError> {
+        if val == 0 {
+            return Err(DateTimeError::ZeroStep);
+        }
+        self.step = val;
+        Ok(self)
+    }
+    /// Sets whether to clear inputs from terminal.
+    ///
+    /// When not cleared the line with the confirmed number is left behind.
+    pub fn clear(&mut self, val: bool) -> &mut Self {
+        self.clear = val;
+        self
+    }
+    /// Sets whether `interact` and `interact_opt` render on stdout or stderr.
+    ///
+    /// Defaults to stderr.
+    pub fn report_stream(&mut self, val: TermTarget) -> &mut Self {
+        self.report_stream = val;
+        self
+    }
+
+    /// Keeps a number within `min` and `max`.
+    fn check_number(&self, num: i64) -> u32 {
+        min(max(num, i64::from(self.min)), i64::from(self.max)) as u32
+    }
+
+    fn terminal_format(&self, num: u32, digits: &[u32], plain: bool) -> String {
+        let value = match digits.is_empty() {
+            true => num.to_string(),
+            false => digits.iter().map(|digit| digit.to_string()).collect::<String>() + "_",
+        };
+        let mut number_str = String::new();
+        write_field(self.theme, &mut number_str, &value, true, plain, Marker::Style);
+        number_str
+    }
+
+    /// Formats the prompt followed by the number.
+    fn line(&self, num: u32, digits: &[u32], plain: bool) -> String {
+        let mut line = String::new();
+        // Writing to a String can not fail.
+        let _ = self.theme.format_datetime(&mut line, &self.prompt, &self.terminal_format(num, digits, plain));
+        line
+    }
+
+    /// Applies a key to the number and the digits typed so far, like `DateTimeSelect::apply_key`.
+    fn apply_key(&self, num: &mut u32, digits: &mut Vec<u32>, key: Key, allow_quit: bool) -> KeyOutcome {
+        let step = match key {
+            Key::ArrowUp | Key::Char('k') => i64::from(self.step),
+            Key::ArrowDown | Key::Char('j') => -i64::from(self.step),
+            _ => 0,
+        };
+        match key {
+            Key::Enter => {
+                if !digits.is_empty() {
+                    *num = self.check_number(typed_value(digits) as i64);
                     digits.clear();
                 }
-                // Decrement the datetime by 1.
-                Key::ArrowDown | Key::Char('k') => {
-                    date_val = match (self.date_type, pos) {
-                        (DateType::DateTime, 0) | (DateType::Date, 0) => date_val.decrement_year(),
-                        (DateType::DateTime, 1) | (DateType::Date, 1) => date_val.decrement_month(),
-                        (DateType::DateTime, 2) | (DateType::Date, 2) => date_val - Duration::days(1),
-                        (DateType::DateTime, 3) | (DateType::Time, 0) => date_val - Duration::hours(1),
-                        (DateType::DateTime, 4) | (DateType::Time, 1) => date_val - Duration::minutes(1),
-                        (DateType::DateTime, 5) | (DateType::Time, 2) => date_val - Duration::seconds(1),
-                        (DateType::Date, _) => panic!("stepped out of bounds on Date"),
-                        (DateType::Time, _) => panic!("stepped out of bounds on Time"),
-                        (DateType::DateTime, _) => panic!("stepped out of bounds on DateTime"),
-                    };
-                    digits = Vec::with_capacity(4);
+                return KeyOutcome::Confirm;
+            }
+            Key::Escape if allow_quit => return KeyOutcome::Cancel,
+            // Stepping stops at min and max, a step that can not move at all is refused.
+            _ if step != 0 => {
+                digits.clear();
+                let stepped = self.check_number(i64::from(*num) + step);
+                if stepped == *num {
+                    return KeyOutcome::Bell;
                 }
-                // Allow numerical inputs.
-                Key::Char(val) => {
-                    if let Some(digit) = val.to_digit(10) {
-                        digits.push(digit);
-                        // Need 4 digits to set year
-                        if pos == 0 && digits.len() == 4 {
-                            let num = digits[0] * 1000 + digits[1] * 100 + digits[2] * 10 + digits[3];
-
-                            date_val = match self.date_type {
-                                DateType::Date | DateType::DateTime => date_val.with_year(num as i32),
-                                DateType::Time => panic!("Time not supported for 4 digits"),
-                            }
-                            .unwrap_or(date_val);
-
-                            digits.clear();
-                        // Have 2 digits in any position, including 0 if hours.
-                        } else if digits.len() == 2 && (pos > 0 || self.date_type == DateType::Time) {
-                            let num = digits[0] * 10 + digits[1];
-                            date_val = match (self.date_type, pos) {
-                                (DateType::DateTime, 1) | (DateType::Date, 1) => date_val.with_month(num),
-                                (DateType::DateTime, 2) | (DateType::Date, 2) => date_val.with_day(num),
-                                (DateType::DateTime, 3) | (DateType::Time, 0) => date_val.with_hour(num),
-                                (DateType::DateTime, 4) | (DateType::Time, 1) => date_val.with_minute(num),
-                                (DateType::DateTime, 5) | (DateType::Time, 2) => date_val.with_second(num),
-                                (DateType::Date, _) => panic!("stepped out of bounds on Date"),
-                                (DateType::Time, _) => panic!("stepped out of bounds on Time"),
-                                (DateType::DateTime, _) => panic!("stepped out of bounds on DateTime"),
-                            }
-                            .unwrap_or(date_val);
-                            digits.clear();
-                        }
-                    } else {
+                *num = stepped;
+            }
+            Key::Home => {
+                *num = self.min;
+                digits.clear();
+            }
+            Key::End => {
+                *num = self.max;
+                digits.clear();
+            }
+            Key::Backspace => {
+                digits.pop();
+            }
+            Key::Char(val) => match val.to_digit(10) {
+                Some(digit) => {
+                    digits.push(digit);
+                    if digits_complete(digits, self.max.to_string().len(), self.max) {
+                        *num = self.check_number(typed_value(digits) as i64);
                         digits.clear();
                     }
                 }
-                Key::Backspace => {
-                    digits.pop();
+                None => digits.clear(),
+            },
+            _ => {}
+        }
+        KeyOutcome::Continue
+    }
+
+    /// Enables user interaction and returns the result.
+    ///
+    /// The dialog is rendered on the terminal of `report_stream`.
+    pub fn interact(&self) -> io::Result<u32> {
+        self.interact_on(&self.term())
+    }
+    /// Enables user interaction and returns the result.
+    ///
+    /// None if the user cancelled with Esc.
+    /// The dialog is rendered on the terminal of `report_stream`.
+    pub fn interact_opt(&self) -> io::Result<Option<u32>> {
+        self._interact_on(&self.term(), true)
+    }
+    /// Like `interact` but allows a specific terminal to be set.
+    pub fn interact_on(&self, term: &Term) -> io::Result<u32> {
+        self._interact_on(term, false)?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Quit not allowed in this case"))
+    }
+    /// Like `interact_opt` but allows a specific terminal to be set.
+    pub fn interact_on_opt(&self, term: &Term) -> io::Result<Option<u32>> {
+        self._interact_on(term, true)
+    }
+    fn _interact_on(&self, term: &Term, allow_quit: bool) -> io::Result<Option<u32>> {
+        if self.max < self.min {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, DateTimeError::InvalidRange));
+        }
+        let mut num = self.check_number(i64::from(self.default));
+        let mut digits: Vec<u32> = Vec::with_capacity(4);
+        // Without colors the number is marked with brackets.
+        let plain = plain_output(term);
+        let _cursor = match term.is_term() {
+            true => Some(HiddenCursor::hide(term)?),
+            false => None,
+        };
+        let mut frame = Frame::default();
+
+        loop {
+            frame.draw(term, vec![self.line(num, &digits, plain)])?;
+
+            let key = match read_key(term) {
+                Ok(key) => key,
+                // Leave no half drawn selection behind when the user presses Ctrl-C.
+                Err(err) => {
+                    if err.kind() == io::ErrorKind::Interrupted {
+                        frame.clear(term, 0)?;
+                    }
+                    return Err(err);
+                }
+            };
+            match self.apply_key(&mut num, &mut digits, key, allow_quit) {
+                KeyOutcome::Continue => {}
+                KeyOutcome::Bell => term.write_str("\x07")?,
+                outcome => {
+                    if self.clear {
+                        frame.clear(term, 0)?;
+                    } else {
+                        // Show the number digits still being typed were set to.
+                        frame.draw(term, vec![self.line(num, &digits, plain)])?;
+                    }
+                    return Ok(match outcome {
+                        KeyOutcome::Confirm => Some(num),
+                        _ => None,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Returns the terminal `interact` and `interact_opt` render on.
+    fn term(&self) -> Term {
+        match self.report_stream {
+            TermTarget::Stdout => Term::stdout(),
+            TermTarget::Stderr => Term::stderr(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults() {
+        let datetime_select = DateTimeSelect::new();
+        assert_eq!(datetime_select.prompt, None);
+        assert_eq!(datetime_select.weekday, true);
+        assert_eq!(datetime_select.date_type, DateType::DateTime);
+    }
+    #[test]
+    fn test_setting_proper_rfc3339_default() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.default("2019-01-01T00:00:00-00:00");
+        assert_eq!(datetime_select.default, Some(NaiveDate::from_ymd(2019, 1, 1).and_hms_milli(0, 0, 0, 0)));
+    }
+    #[test]
+    fn test_setting_naive_default() {
+        let mut datetime_select = DateTimeSelect::new();
+        let date = NaiveDate::from_ymd(2019, 1, 1).and_hms(12, 30, 0);
+        datetime_select.default_datetime(date);
+        assert_eq!(datetime_select.default, Some(date));
+    }
+    #[test]
+    fn test_setting_prompt() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.with_prompt("test");
+        assert_eq!(datetime_select.prompt, Some("test".to_owned()));
+    }
+    #[test]
+    fn test_setting_weekday() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.weekday(false);
+        assert_eq!(datetime_select.weekday, false);
+    }
+    #[test]
+    fn test_setting_valid_date_type() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.date_type(DateType::Date);
+        assert_eq!(datetime_select.date_type, DateType::Date);
+    }
+    #[test]
+    fn test_max_min_datetimes() {
+        let mut datetime_select = DateTimeSelect::new();
+
+        datetime_select.min("2020-02-20T02:20:25Z");
+        let min_date = NaiveDate::from_ymd(2020, 2, 20).and_hms(2, 20, 25);
+        assert_eq!(datetime_select.min, min_date);
+
+        datetime_select.max("2022-11-30T00:00:00Z");
+        let max_date = NaiveDate::from_ymd(2022, 11, 30).and_hms(0, 0, 0);
+        assert_eq!(datetime_select.max, max_date);
+
+        let in_range_date = NaiveDate::from_ymd(2020, 7, 8).and_hms(17, 1, 30);
+        assert_eq!(datetime_select.check_date(in_range_date), in_range_date);
+
+        assert_eq!(datetime_select.check_date(NaiveDate::from_ymd(2000, 1, 1).and_hms(0, 0, 0)), min_date);
+        assert_eq!(datetime_select.check_date(NaiveDate::from_ymd(2030, 1, 1).and_hms(0, 0, 0)), max_date);
+    }
+    #[test]
+    fn test_fallible_setters() {
+        let mut datetime_select = DateTimeSelect::new();
+        assert!(datetime_select.try_default("2019-01-01").is_err());
+        assert!(datetime_select.try_min("not a date").is_err());
+        assert_eq!(datetime_select.default, None);
+
+        datetime_select.try_min("2020-02-20T02:20:25Z").unwrap();
+        datetime_select.try_max("2019-01-01T00:00:00Z").unwrap();
+        match datetime_select.check_range() {
+            Err(DateTimeError::InvalidRange) => {}
+            _ => panic!("expected an invalid range error"),
+        }
+    }
+    #[test]
+    fn test_date_only_bounds() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.min("2024-01-01").max("2024-12-31");
+        assert_eq!(datetime_select.min, NaiveDate::from_ymd(2024, 1, 1).and_hms(0, 0, 0));
+        assert_eq!(datetime_select.max, NaiveDate::from_ymd(2024, 12, 31).and_hms(23, 59, 59));
+        datetime_select.max("2025-06-30T12:00:00Z");
+        assert_eq!(datetime_select.max, NaiveDate::from_ymd(2025, 6, 30).and_hms(12, 0, 0));
+        assert!(datetime_select.try_min("2024-13-01").is_err());
+    }
+    #[test]
+    fn test_parse_bound() {
+        assert_eq!(parse_bound("2020-07-08T17:01:30+02:00").unwrap(), NaiveDate::from_ymd(2020, 7, 8).and_hms(17, 1, 30));
+        // Plain dates are accepted like min does.
+        assert_eq!(parse_bound("2020-07-08").unwrap(), NaiveDate::from_ymd(2020, 7, 8).and_hms(0, 0, 0));
+        match parse_bound("2020-07-08 17:01") {
+            Err(DateTimeError::Parse(_)) => {}
+            _ => panic!("expected a parse error"),
+        }
+        assert!(is_valid_rfc3339("2020-07-08T17:01:30Z"));
+        assert!(!is_valid_rfc3339("2020-07-08 17:01"));
+        // default needs a full RFC3339 string.
+        assert!(!is_valid_rfc3339("2020-07-08"));
+    }
+    #[test]
+    fn test_format_result() {
+        let mut datetime_select = DateTimeSelect::new();
+        let date = NaiveDate::from_ymd(2020, 7, 8).and_hms(17, 1, 30);
+        assert_eq!(datetime_select.format_result(date, datetime_select.timezone), "2020-07-08T17:01:30Z");
+
+        datetime_select.date_type(DateType::Date);
+        assert_eq!(datetime_select.format_result(date, datetime_select.timezone), "2020-07-08");
+
+        datetime_select.date_type(DateType::Time);
+        assert_eq!(datetime_select.format_result(date, datetime_select.timezone), "17:01:30");
+
+        datetime_select.date_type(DateType::DateTime).timezone(Zone::Fixed(FixedOffset::east(2 * 3600)));
+        assert_eq!(datetime_select.format_result(date, datetime_select.timezone), "2020-07-08T17:01:30+02:00");
+        assert_eq!(datetime_select.localize(date, datetime_select.timezone).timestamp(), 1_594_220_490);
+
+        datetime_select.output_format("%d/%m/%Y");
+        assert_eq!(datetime_select.format_result(date, datetime_select.timezone), "08/07/2020");
+    }
+    #[test]
+    fn test_emit_offset() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.emit_offset(false);
+        let date = NaiveDate::from_ymd(2024, 3, 7).and_hms_milli(12, 0, 0, 250);
+        assert_eq!(datetime_select.format_result(date, Zone::Fixed(FixedOffset::east(3600))), "2024-03-07T12:00:00");
+        datetime_select.seconds_format(SecondsFormat::Millis);
+        assert_eq!(datetime_select.format_result(date, Zone::Utc), "2024-03-07T12:00:00.250");
+        datetime_select.hour12(true);
+        assert_eq!(datetime_select.format_result(date, Zone::Utc), "2024-03-07 12:00:00.250 PM");
+    }
+    #[test]
+    fn test_format_result_hour12() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.date_type(DateType::Time).hour12(true);
+        let date = NaiveDate::from_ymd(2020, 7, 8).and_hms(13, 0, 0);
+        assert_eq!(datetime_select.format_result(date, Zone::Utc), "01:00:00 PM");
+        let date = NaiveDate::from_ymd(2020, 7, 8).and_hms(0, 15, 0);
+        assert_eq!(datetime_select.format_result(date, Zone::Utc), "12:15:00 AM");
+
+        datetime_select.subsecond(Precision::Millis);
+        assert_eq!(datetime_select.format_result(date, Zone::Utc), "12:15:00.000 AM");
+
+        datetime_select.date_type(DateType::DateTime).subsecond(Precision::Seconds);
+        let zone = Zone::Fixed(FixedOffset::east(2 * 3600));
+        assert_eq!(datetime_select.format_result(date, zone), "2020-07-08 12:15:00 AM +02:00");
+        datetime_select.seconds_format(SecondsFormat::Millis);
+        assert_eq!(datetime_select.format_result(date, zone), "2020-07-08 12:15:00.000 AM +02:00");
+    }
+    #[test]
+    fn test_month_steps_in_leap_years() {
+        let date = NaiveDate::from_ymd(2020, 1, 30).and_hms(0, 0, 0);
+        assert_eq!(date.increment_month(), NaiveDate::from_ymd(2020, 2, 29).and_hms(0, 0, 0));
+        let date = NaiveDate::from_ymd(2020, 3, 31).and_hms(0, 0, 0);
+        assert_eq!(date.decrement_month(), NaiveDate::from_ymd(2020, 2, 29).and_hms(0, 0, 0));
+        let date = NaiveDate::from_ymd(2019, 1, 31).and_hms(0, 0, 0);
+        assert_eq!(date.increment_month(), NaiveDate::from_ymd(2019, 2, 28).and_hms(0, 0, 0));
+        let date = NaiveDate::from_ymd(2019, 12, 31).and_hms(0, 0, 0);
+        assert_eq!(date.increment_month(), NaiveDate::from_ymd(2020, 1, 31).and_hms(0, 0, 0));
+        assert_eq!(date.increment_month().decrement_month(), date);
+        assert_eq!(days_in_month(2000, 2), 29);
+        assert_eq!(days_in_month(1900, 2), 28);
+        assert_eq!(days_in_month(2024, 2), 29);
+        assert_eq!(days_in_month(2023, 4), 30);
+        assert_eq!(days_in_month(2023, 12), 31);
+    }
+    #[test]
+    fn test_field_steps() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.step_minutes(15).step(DateField::Month, 3);
+        datetime_select.max("2020-07-08T17:50:00Z");
+
+        let date = NaiveDate::from_ymd(2020, 1, 15).and_hms(17, 30, 0);
+        assert_eq!(datetime_select.step_field(date, DateField::Minute, true), NaiveDate::from_ymd(2020, 1, 15).and_hms(17, 45, 0));
+        assert_eq!(datetime_select.step_field(date, DateField::Minute, false), NaiveDate::from_ymd(2020, 1, 15).and_hms(17, 15, 0));
+        assert_eq!(datetime_select.step_field(date, DateField::Second, true), NaiveDate::from_ymd(2020, 1, 15).and_hms(17, 30, 1));
+        assert_eq!(datetime_select.step_field(date, DateField::Month, true), NaiveDate::from_ymd(2020, 4, 15).and_hms(17, 30, 0));
+
+        // Steps overshooting the maximum land exactly on it.
+        let near_max = NaiveDate::from_ymd(2020, 7, 8).and_hms(17, 45, 0);
+        let stepped = datetime_select.step_field(near_max, DateField::Minute, true);
+        assert_eq!(datetime_select.check_date(stepped), datetime_select.max);
+
+        match datetime_select.try_step(DateField::Minute, 0) {
+            Err(DateTimeError::ZeroStep) => {}
+            _ => panic!("expected a zero step error"),
+        }
+        assert_eq!(datetime_select.steps[&DateField::Minute], 15);
+    }
+    #[test]
+    fn test_coarse_jumps() {
+        let datetime_select = DateTimeSelect::new();
+        let date = NaiveDate::from_ymd(2020, 1, 15).and_hms(17, 30, 0);
+        assert_eq!(datetime_select.jump_field(date, DateField::Year, true), NaiveDate::from_ymd(2030, 1, 15).and_hms(17, 30, 0));
+        assert_eq!(datetime_select.jump_field(date, DateField::Month, false), NaiveDate::from_ymd(2019, 10, 15).and_hms(17, 30, 0));
+        assert_eq!(datetime_select.jump_field(date, DateField::Day, true), NaiveDate::from_ymd(2020, 1, 22).and_hms(17, 30, 0));
+        assert_eq!(datetime_select.jump_field(date, DateField::Minute, true), NaiveDate::from_ymd(2020, 1, 15).and_hms(17, 40, 0));
+    }
+    #[test]
+    fn test_delete_key() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.default("2020-06-15T10:30:45+00:00");
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Tab, Key::Char('2'), Key::Del]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 6, 1).and_hms(10, 30, 45));
+        assert!(state.digits.is_empty());
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Tab, Key::Tab, Key::Tab, Key::Del]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 6, 15).and_hms(10, 0, 45));
+
+        // The cleared field is still kept within min.
+        datetime_select.min("2020-06-10T00:00:00+00:00");
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Tab, Key::Del]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 6, 10).and_hms(0, 0, 0));
+    }
+    #[test]
+    fn test_field_bounds() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.min("2010-01-01T00:00:00Z").max("2030-12-31T00:00:00Z");
+        let date = NaiveDate::from_ymd(2020, 2, 15).and_hms(17, 30, 10);
+        assert_eq!(datetime_select.bound_field(date, DateField::Year, false), NaiveDate::from_ymd(2010, 2, 15).and_hms(17, 30, 10));
+        assert_eq!(datetime_select.bound_field(date, DateField::Year, true), NaiveDate::from_ymd(2030, 2, 15).and_hms(17, 30, 10));
+        assert_eq!(datetime_select.bound_field(date, DateField::Day, true), NaiveDate::from_ymd(2020, 2, 29).and_hms(17, 30, 10));
+        assert_eq!(datetime_select.bound_field(date, DateField::Month, true), NaiveDate::from_ymd(2020, 12, 15).and_hms(17, 30, 10));
+        assert_eq!(datetime_select.bound_field(date, DateField::Hour, false), NaiveDate::from_ymd(2020, 2, 15).and_hms(0, 30, 10));
+        assert_eq!(datetime_select.bound_field(date, DateField::Second, true), NaiveDate::from_ymd(2020, 2, 15).and_hms(17, 30, 59));
+
+        datetime_select.hour12(true);
+        assert_eq!(datetime_select.bound_field(date, DateField::Hour, true), NaiveDate::from_ymd(2020, 2, 15).and_hms(23, 30, 10));
+        assert_eq!(datetime_select.bound_field(date, DateField::Meridiem, false), NaiveDate::from_ymd(2020, 2, 15).and_hms(5, 30, 10));
+    }
+    #[test]
+    fn test_wrap_fields() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.wrap_fields(true);
+        let date = NaiveDate::from_ymd(2020, 12, 31).and_hms(23, 59, 59);
+        assert_eq!(datetime_select.step_field(date, DateField::Second, true), NaiveDate::from_ymd(2020, 12, 31).and_hms(23, 59, 0));
+        assert_eq!(datetime_select.step_field(date, DateField::Minute, true), NaiveDate::from_ymd(2020, 12, 31).and_hms(23, 0, 59));
+        assert_eq!(datetime_select.step_field(date, DateField::Hour, true), NaiveDate::from_ymd(2020, 12, 31).and_hms(0, 59, 59));
+        assert_eq!(datetime_select.step_field(date, DateField::Day, true), NaiveDate::from_ymd(2020, 12, 1).and_hms(23, 59, 59));
+        assert_eq!(datetime_select.step_field(date, DateField::Month, true), NaiveDate::from_ymd(2020, 1, 31).and_hms(23, 59, 59));
+        assert_eq!(datetime_select.step_field(date, DateField::Year, true), NaiveDate::from_ymd(2021, 12, 31).and_hms(23, 59, 59));
+
+        let date = NaiveDate::from_ymd(2020, 1, 31).and_hms(0, 0, 0);
+        assert_eq!(datetime_select.step_field(date, DateField::Month, false), NaiveDate::from_ymd(2020, 12, 31).and_hms(0, 0, 0));
+        assert_eq!(datetime_select.step_field(date, DateField::Day, false), NaiveDate::from_ymd(2020, 1, 30).and_hms(0, 0, 0));
+        assert_eq!(datetime_select.step_field(date, DateField::Hour, false), NaiveDate::from_ymd(2020, 1, 31).and_hms(23, 0, 0));
+        let date = NaiveDate::from_ymd(2020, 3, 31).and_hms(0, 0, 0);
+        assert_eq!(datetime_select.step_field(date, DateField::Month, false), NaiveDate::from_ymd(2020, 2, 29).and_hms(0, 0, 0));
+    }
+    #[test]
+    fn test_clamp_typed_values() {
+        let mut datetime_select = DateTimeSelect::new();
+        let date = NaiveDate::from_ymd(2020, 4, 15).and_hms(17, 30, 0);
+        assert_eq!(datetime_select.clamp_field(date, DateField::Month, 13), NaiveDate::from_ymd(2020, 12, 15).and_hms(17, 30, 0));
+        assert_eq!(datetime_select.clamp_field(date, DateField::Day, 31), NaiveDate::from_ymd(2020, 4, 30).and_hms(17, 30, 0));
+        assert_eq!(datetime_select.clamp_field(date, DateField::Day, 0), NaiveDate::from_ymd(2020, 4, 1).and_hms(17, 30, 0));
+        assert_eq!(datetime_select.clamp_field(date, DateField::Hour, 99), NaiveDate::from_ymd(2020, 4, 15).and_hms(23, 30, 0));
+
+        let leap_day = NaiveDate::from_ymd(2020, 2, 29).and_hms(0, 0, 0);
+        assert_eq!(datetime_select.clamp_field(leap_day, DateField::Year, 2021), NaiveDate::from_ymd(2021, 2, 28).and_hms(0, 0, 0));
+
+        datetime_select.hour12(true);
+        assert_eq!(datetime_select.clamp_field(date, DateField::Hour, 0), NaiveDate::from_ymd(2020, 4, 15).and_hms(13, 30, 0));
+    }
+    #[test]
+    fn test_months_matching() {
+        let datetime_select = DateTimeSelect::new();
+        assert_eq!(datetime_select.months_matching("f"), vec![2]);
+        assert_eq!(datetime_select.months_matching("J"), vec![1, 6, 7]);
+        assert_eq!(datetime_select.months_matching("ju"), vec![6, 7]);
+        assert_eq!(datetime_select.months_matching("jul"), vec![7]);
+        assert!(datetime_select.months_matching("l").is_empty());
+
+        let date = NaiveDate::from_ymd(2020, 1, 31).and_hms(0, 0, 0);
+        assert_eq!(with_month_clamped(date, 2), NaiveDate::from_ymd(2020, 2, 29).and_hms(0, 0, 0));
+    }
+    #[test]
+    fn test_month_names() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.date_type(DateType::Date).month_names(true);
+        let date = NaiveDate::from_ymd(2024, 3, 7).and_hms(0, 0, 0);
+        assert_eq!(console::strip_ansi_codes(&datetime_select.terminal_format(date, Zone::Utc, 0, &[], false)), "2024-Mar-07");
+    }
+    #[test]
+    fn test_locale_names() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.date_type(DateType::Date).month_names(true).locale("de_DE.UTF-8");
+        let date = NaiveDate::from_ymd(2024, 3, 7).and_hms(0, 0, 0);
+        assert_eq!(console::strip_ansi_codes(&datetime_select.terminal_format(date, Zone::Utc, 0, &[], false)), "2024-Mär-07");
+        assert_eq!(datetime_select.months_matching("mä"), vec![3]);
+
+        datetime_select.locale("xx");
+        assert_eq!(console::strip_ansi_codes(&datetime_select.terminal_format(date, Zone::Utc, 0, &[], false)), "2024-Mar-07");
+    }
+    #[test]
+    fn test_typed_month_name() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.date_type(DateType::Date).default("2024-05-07T00:00:00Z");
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Char('f')]);
+        assert_eq!(state.date_val.month(), 2);
+        // "r" continues a name, it only resets when a name is not being typed.
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Char('m'), Key::Char('a'), Key::Char('r')]);
+        assert_eq!(state.date_val.month(), 3);
+        // Month names win over bindings that share their first letter.
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Char('j')]);
+        assert_eq!(state.date_val.month(), 1);
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Char('j'), Key::Char('u'), Key::Char('n')]);
+        assert_eq!(state.date_val.month(), 6);
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Char('J'), Key::Char('u'), Key::Char('l')]);
+        assert_eq!(state.date_val.month(), 7);
+        // Letters no month starts with keep their binding.
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Char('k')]);
+        assert_eq!(state.date_val.month(), 6);
+    }
+    #[test]
+    fn test_weekday_format() {
+        let mut datetime_select = DateTimeSelect::new();
+        let date = NaiveDate::from_ymd(2024, 3, 6).and_hms(0, 0, 0);
+        assert_eq!(datetime_select.weekday_name(date), "Wed");
+        datetime_select.weekday_format(WeekdayFormat::Long);
+        assert_eq!(datetime_select.weekday_name(date), "Wednesday");
+        datetime_select.locale("fr");
+        assert_eq!(datetime_select.weekday_name(date), "mercredi");
+    }
+    #[test]
+    fn test_custom_separators() {
+        let mut datetime_select = DateTimeSelect::new();
+        let date = NaiveDate::from_ymd(2024, 3, 7).and_hms(12, 5, 0);
+        assert_eq!(console::strip_ansi_codes(&datetime_select.terminal_format(date, Zone::Utc, 0, &[], false)), "2024-03-07 12:05:00");
+        datetime_select.date_separator("/").time_separator(".").datetime_separator("T");
+        assert_eq!(console::strip_ansi_codes(&datetime_select.terminal_format(date, Zone::Utc, 0, &[], false)), "2024/03/07T12.05.00");
+    }
+    #[test]
+    fn test_hour12_fields() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.date_type(DateType::Time).hour12(true);
+        assert_eq!(datetime_select.fields(), vec![DateField::Hour, DateField::Minute, DateField::Second, DateField::Meridiem]);
+
+        let date = NaiveDate::from_ymd(2020, 7, 8).and_hms(17, 1, 30);
+        let toggled = datetime_select.step_field(date, DateField::Meridiem, true);
+        assert_eq!(toggled, NaiveDate::from_ymd(2020, 7, 8).and_hms(5, 1, 30));
+        assert_eq!(datetime_select.set_field(date, DateField::Hour, 12), Some(NaiveDate::from_ymd(2020, 7, 8).and_hms(12, 1, 30)));
+        assert_eq!(datetime_select.set_field(toggled, DateField::Hour, 12), Some(NaiveDate::from_ymd(2020, 7, 8).and_hms(0, 1, 30)));
+        assert_eq!(datetime_select.set_field(date, DateField::Hour, 13), None);
+
+        datetime_select.date_type(DateType::Date);
+        assert_eq!(datetime_select.fields(), vec![DateField::Year, DateField::Month, DateField::Day]);
+    }
+    #[test]
+    #[cfg(feature = "chrono-tz")]
+    fn test_named_timezones() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.select_timezone(true);
+        assert_eq!(datetime_select.fields().last(), Some(&DateField::Timezone));
+
+        let zone = Zone::Named(Tz::Europe__Paris);
+        let summer = NaiveDate::from_ymd(2020, 7, 8).and_hms(17, 1, 30);
+        let winter = NaiveDate::from_ymd(2020, 1, 8).and_hms(17, 1, 30);
+        assert_eq!(datetime_select.format_result(summer, zone), "2020-07-08T17:01:30+02:00");
+        assert_eq!(datetime_select.format_result(winter, zone), "2020-01-08T17:01:30+01:00");
+        assert!(console::strip_ansi_codes(&datetime_select.terminal_format(summer, zone, 0, &[], false)).ends_with(" Europe/Paris"));
+
+        assert_eq!(zone.cycle(true).cycle(false), zone);
+        assert_eq!(Zone::Utc.cycle(true), Zone::Named(Tz::UTC));
+        assert_eq!(Zone::Named(Tz::UTC).cycle(false), Zone::Named(Tz::Pacific__Auckland));
+    }
+    #[test]
+    fn test_subsecond_precision() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.subsecond(Precision::Millis);
+        assert_eq!(datetime_select.fields().last(), Some(&DateField::Millisecond));
+
+        let date = NaiveDate::from_ymd(2020, 7, 8).and_hms_milli(17, 1, 30, 250);
+        assert_eq!(datetime_select.format_result(date, Zone::Utc), "2020-07-08T17:01:30.250Z");
+        assert_eq!(console::strip_ansi_codes(&datetime_select.terminal_format(date, Zone::Utc, 0, &[], false)), "2020-07-08 17:01:30.250");
+        assert_eq!(datetime_select.step_field(date, DateField::Millisecond, false), NaiveDate::from_ymd(2020, 7, 8).and_hms_milli(17, 1, 30, 249));
+        assert_eq!(datetime_select.set_field(date, DateField::Millisecond, 1000), None);
+
+        datetime_select.subsecond(Precision::Nanos).date_type(DateType::Time);
+        let date = NaiveDate::from_ymd(2020, 7, 8).and_hms_nano(17, 1, 30, 5);
+        assert_eq!(datetime_select.format_result(date, Zone::Utc), "17:01:30.000000005");
+        let date = datetime_select.bound_field(date, DateField::Nanosecond, true);
+        assert_eq!(datetime_select.step_field(date, DateField::Nanosecond, true), NaiveDate::from_ymd(2020, 7, 8).and_hms(17, 1, 31));
+    }
+    #[test]
+    fn test_hidden_seconds() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.default("2020-07-08T17:01:30Z").show_seconds(false).subsecond(Precision::Millis);
+        assert_eq!(datetime_select.fields().last(), Some(&DateField::Minute));
+
+        let date = NaiveDate::from_ymd(2020, 7, 8).and_hms(17, 1, 30);
+        assert_eq!(console::strip_ansi_codes(&datetime_select.terminal_format(date, Zone::Utc, 0, &[], false)), "2020-07-08 17:01");
+        let state = apply_keys(&datetime_select, vec![Key::Enter]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 7, 8).and_hms(17, 1, 0));
+    }
+    #[test]
+    fn test_year_month() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.default("2020-01-31T17:01:30Z").date_type(DateType::YearMonth);
+        assert_eq!(datetime_select.fields(), vec![DateField::Year, DateField::Month]);
+
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::ArrowUp]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 2, 1).and_hms(0, 0, 0));
+        assert_eq!(datetime_select.format_result(state.date_val, Zone::Utc), "2020-02");
+        assert_eq!(console::strip_ansi_codes(&datetime_select.terminal_format(state.date_val, Zone::Utc, 0, &[], false)), "2020-02");
+    }
+    #[test]
+    fn test_iso_week() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.default("2024-03-04T00:00:00Z").date_type(DateType::IsoWeek);
+        let date = NaiveDate::from_ymd(2024, 3, 4).and_hms(0, 0, 0);
+        assert_eq!(datetime_select.format_result(date, Zone::Utc), "2024-W10-1");
+        assert_eq!(console::strip_ansi_codes(&datetime_select.terminal_format(date, Zone::Utc, 0, &[], false)), "2024-W10-1");
+
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Char('5'), Key::Char('3')]);
+        assert_eq!(state.date_val, date);
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Char('5'), Key::Char('2'), Key::Tab, Key::Char('7')]);
+        assert_eq!(datetime_select.format_result(state.date_val, Zone::Utc), "2024-W52-7");
+
+        // 2020 has 53 weeks, 2021 only 52.
+        let date = NaiveDate::from_ymd(2020, 12, 28).and_hms(0, 0, 0);
+        assert_eq!(datetime_select.format_result(date, Zone::Utc), "2020-W53-1");
+        let date = datetime_select.step_field(date, DateField::IsoYear, true);
+        assert_eq!(datetime_select.format_result(date, Zone::Utc), "2021-W52-1");
+    }
+    #[test]
+    fn test_quarter_select() {
+        let mut quarter_select = QuarterSelect::new();
+        quarter_select.default(NaiveDate::from_ymd(2024, 8, 15));
+        assert_eq!(QuarterSelect::split(2024 * 4 + 2), (2024, 3));
+        assert_eq!(QuarterSelect::split(2024 * 4 + 4), (2025, 1));
+        assert_eq!(console::strip_ansi_codes(&quarter_select.terminal_format(2024 * 4 + 2, 0, &[], false)), "Q3 2024");
+        assert_eq!(quarter_select.terminal_format(2024 * 4 + 2, 1, &[], true), "Q3 [2024]");
+        assert_eq!(quarter_select.terminal_format(2024 * 4 + 2, 1, &[2, 0], true), "Q3 [20__]");
+        assert_eq!(first_day_of_quarter(2024, 3), NaiveDate::from_ymd(2024, 7, 1));
+        assert_eq!(first_day_of_quarter(2024, 4), NaiveDate::from_ymd(2024, 10, 1));
+    }
+    #[test]
+    fn test_quarter_keys() {
+        let mut quarter_select = QuarterSelect::new();
+        let keys = |quarter_select: &QuarterSelect, keys: Vec<Key>| {
+            let (mut quarters, mut pos, mut digits) = (2024 * 4 + 2, 0, Vec::new());
+            let outcomes: Vec<KeyOutcome> =
+                keys.into_iter().map(|key| quarter_select.apply_key(&mut quarters, &mut pos, &mut digits, key, true)).collect();
+            (QuarterSelect::split(quarters), digits, outcomes)
+        };
+        assert_eq!(keys(&quarter_select, vec![Key::Char('1')]).0, (2024, 1));
+        // Quarters outside 1 to 4 are ignored.
+        assert_eq!(keys(&quarter_select, vec![Key::Char('5')]).0, (2024, 3));
+        // Stepping the quarter carries over into the year, the year field steps by a year.
+        assert_eq!(keys(&quarter_select, vec![Key::ArrowUp, Key::ArrowUp]).0, (2025, 1));
+        assert_eq!(keys(&quarter_select, vec![Key::Tab, Key::Char('j')]).0, (2023, 3));
+        assert_eq!(keys(&quarter_select, vec![Key::Tab, Key::Char('1'), Key::Char('9')]).1, vec![1, 9]);
+        assert_eq!(keys(&quarter_select, vec![Key::Tab, Key::Char('1'), Key::Char('9'), Key::Char('9'), Key::Char('9')]).0, (1999, 3));
+        // Digits still being typed are set on confirm.
+        let (selected, digits, outcomes) = keys(&quarter_select, vec![Key::Tab, Key::Char('9'), Key::Char('9'), Key::Enter]);
+        assert_eq!((selected, digits), ((99, 3), vec![]));
+        assert_eq!(outcomes.last(), Some(&KeyOutcome::Confirm));
+        assert_eq!(keys(&quarter_select, vec![Key::Escape]).2, vec![KeyOutcome::Cancel]);
+
+        quarter_select.confirm_key(Key::Char(' '));
+        assert_eq!(keys(&quarter_select, vec![Key::Enter]).2, vec![KeyOutcome::Continue]);
+        assert_eq!(keys(&quarter_select, vec![Key::Char(' ')]).2, vec![KeyOutcome::Confirm]);
+    }
+    #[test]
+    fn test_seconds_format() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.default("2020-07-08T17:01:30Z").seconds_format(SecondsFormat::Millis);
+        let val = NaiveDate::from_ymd(2020, 7, 8).and_hms(17, 1, 30);
+        assert_eq!(datetime_select.format_result(val, Zone::Utc), "2020-07-08T17:01:30.000Z");
+
+        datetime_select.seconds_format(SecondsFormat::AutoSi);
+        assert_eq!(datetime_select.format_result(val, Zone::Utc), "2020-07-08T17:01:30Z");
+    }
+    #[test]
+    fn test_calendar_lines() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.calendar_view(true);
+        let date = NaiveDate::from_ymd(2024, 2, 14).and_hms(0, 0, 0);
+        let lines: Vec<String> =
+            datetime_select.calendar_lines(date).iter().map(|line| console::strip_ansi_codes(line).to_string()).collect();
+        assert_eq!(
+            lines,
+            vec![
+                " Mo  Tu  We  Th  Fr  Sa  Su ",
+                "              1   2   3   4",
+                "  5   6   7   8   9  10  11",
+                " 12  13 [14] 15  16  17  18",
+                " 19  20  21  22  23  24  25",
+                " 26  27  28  29",
+            ]
+        );
+    }
+    #[test]
+    fn test_duration_fields() {
+        let mut duration_select = DurationSelect::new();
+        let total = 26 * 3600 + 5 * 60 + 9;
+        assert_eq!(console::strip_ansi_codes(&duration_select.terminal_format(total, 0, &[], false)), "26:05:09");
+        assert_eq!(duration_select.terminal_format(total, 1, &[3], true), "26:[3_]:09");
+        assert_eq!(duration_select.set_field(total, DateField::Hour, 99), Some(99 * 3600 + 5 * 60 + 9));
+        assert_eq!(duration_select.set_field(total, DateField::Minute, 30), Some(26 * 3600 + 30 * 60 + 9));
+        assert_eq!(duration_select.set_field(total, DateField::Second, 60), None);
+
+        duration_select.min(Duration::minutes(1)).max(Duration::hours(24));
+        assert_eq!(duration_select.check_seconds(0), 60);
+        assert_eq!(duration_select.check_seconds(total), 24 * 3600);
+    }
+    #[test]
+    fn test_duration_keys() {
+        let mut duration_select = DurationSelect::new();
+        duration_select.max(Duration::hours(500));
+        let keys = |duration_select: &DurationSelect, keys: Vec<Key>| {
+            let (mut total, mut pos, mut digits) = (3600, 0, Vec::new());
+            let outcomes: Vec<KeyOutcome> =
+                keys.into_iter().map(|key| duration_select.apply_key(&mut total, &mut pos, &mut digits, key, true)).collect();
+            (total, pos, digits, outcomes)
+        };
+        // Hours take as many digits as max has.
+        assert_eq!(keys(&duration_select, vec![Key::Char('1'), Key::Char('2')]).2, vec![1, 2]);
+        assert_eq!(keys(&duration_select, vec![Key::Char('0'), Key::Char('1'), Key::Char('2')]).0, 12 * 3600);
+        // A first 6 can only become 60 or more, past the minutes.
+        assert_eq!(keys(&duration_select, vec![Key::Tab, Key::Char('6')]).0, 3600 + 6 * 60);
+        assert_eq!(keys(&duration_select, vec![Key::Char('l'), Key::Char('l'), Key::Char('4'), Key::Char('5')]).0, 3600 + 45);
+        assert_eq!(keys(&duration_select, vec![Key::ArrowLeft, Key::ArrowUp]).0, 3600 + 1);
+        // Digits still being typed are set on confirm.
+        let (total, _, digits, outcomes) = keys(&duration_select, vec![Key::Tab, Key::Char('3'), Key::Enter]);
+        assert_eq!((total, digits), (3600 + 3 * 60, vec![]));
+        assert_eq!(outcomes.last(), Some(&KeyOutcome::Confirm));
+        // Stepping stops at min, only the step that can not move is refused.
+        let (total, _, _, outcomes) = keys(&duration_select, vec![Key::ArrowDown, Key::ArrowDown, Key::Escape]);
+        assert_eq!(total, 0);
+        assert_eq!(outcomes, vec![KeyOutcome::Continue, KeyOutcome::Bell, KeyOutcome::Cancel]);
+
+        duration_select.confirm_key(Key::Char(' '));
+        assert_eq!(keys(&duration_select, vec![Key::Enter]).3, vec![KeyOutcome::Continue]);
+        assert_eq!(keys(&duration_select, vec![Key::Char(' ')]).3, vec![KeyOutcome::Confirm]);
+
+        duration_select.min(Duration::hours(501));
+        assert_eq!(duration_select.interact_on(&Term::stderr()).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+    #[test]
+    fn test_disabled_dates() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select
+            .date_type(DateType::Date)
+            .disabled_dates(vec![NaiveDate::from_ymd(2020, 7, 9)])
+            .disable_if(|date| date.day() == 10);
+        let date = NaiveDate::from_ymd(2020, 7, 8).and_hms(0, 0, 0);
+        assert!(datetime_select.is_disabled(NaiveDate::from_ymd(2020, 7, 9).and_hms(0, 0, 0)));
+        assert!(datetime_select.is_disabled(NaiveDate::from_ymd(2020, 8, 10).and_hms(0, 0, 0)));
+        assert!(!datetime_select.is_disabled(date));
+
+        let stepped = datetime_select.skip_disabled(date, DateField::Day, |val| datetime_select.step_field(val, DateField::Day, true));
+        assert_eq!(stepped, NaiveDate::from_ymd(2020, 7, 11).and_hms(0, 0, 0));
+
+        // Stepping stops at the bound even if it is disabled.
+        datetime_select.max = NaiveDate::from_ymd(2020, 7, 10).and_hms(0, 0, 0);
+        let stepped = datetime_select.skip_disabled(date, DateField::Day, |val| datetime_select.step_field(val, DateField::Day, true));
+        assert_eq!(stepped, NaiveDate::from_ymd(2020, 7, 10).and_hms(0, 0, 0));
+    }
+    #[test]
+    fn test_weekdays_only() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.weekdays_only(true);
+        let friday = NaiveDate::from_ymd(2020, 7, 10).and_hms(0, 0, 0);
+        let monday = NaiveDate::from_ymd(2020, 7, 13).and_hms(0, 0, 0);
+        assert!(datetime_select.is_disabled(NaiveDate::from_ymd(2020, 7, 11).and_hms(0, 0, 0)));
+        assert_eq!(datetime_select.skip_disabled(friday, DateField::Day, |val| datetime_select.step_field(val, DateField::Day, true)), monday);
+        assert_eq!(datetime_select.skip_disabled(monday, DateField::Day, |val| datetime_select.step_field(val, DateField::Day, false)), friday);
+
+        datetime_select.max = NaiveDate::from_ymd(2020, 7, 12).and_hms(0, 0, 0);
+        let stepped = datetime_select.skip_disabled(friday, DateField::Day, |val| datetime_select.step_field(val, DateField::Day, true));
+        assert_eq!(stepped, datetime_select.max);
+    }
+    #[test]
+    fn test_week_start() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.week_start(Weekday::Sun);
+        let date = NaiveDate::from_ymd(2024, 9, 30).and_hms(0, 0, 0);
+        let lines: Vec<String> =
+            datetime_select.calendar_lines(date).iter().map(|line| console::strip_ansi_codes(line).to_string()).collect();
+        assert_eq!(lines[0], " Su  Mo  Tu  We  Th  Fr  Sa ");
+        assert_eq!(lines[1], "  1   2   3   4   5   6   7");
+        assert_eq!(lines[5], " 29 [30]");
+    }
+    #[test]
+    fn test_clamp_mode() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.min("2020-03-15T10:20:30Z").max("2025-06-15T00:00:00Z");
+        let below = NaiveDate::from_ymd(2019, 5, 1).and_hms(8, 0, 0);
+        let above = NaiveDate::from_ymd(2026, 5, 1).and_hms(8, 0, 0);
+        assert_eq!(datetime_select.check_field(below, DateField::Year), datetime_select.min);
+
+        datetime_select.clamp_mode(ClampMode::SnapToField);
+        assert_eq!(datetime_select.check_field(below, DateField::Year), NaiveDate::from_ymd(2020, 5, 1).and_hms(8, 0, 0));
+        assert_eq!(datetime_select.check_field(above, DateField::Year), NaiveDate::from_ymd(2025, 5, 1).and_hms(8, 0, 0));
+        // The other fields are still too early in the min year, so the year moves one further.
+        let early = NaiveDate::from_ymd(2019, 1, 1).and_hms(8, 0, 0);
+        assert_eq!(datetime_select.check_field(early, DateField::Year), NaiveDate::from_ymd(2021, 1, 1).and_hms(8, 0, 0));
+        // Falls back to the bound when the field alone can not bring the value into range.
+        assert_eq!(datetime_select.check_field(below, DateField::Month), datetime_select.min);
+    }
+    #[test]
+    fn test_validate_with() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select
+            .validate_with(|val: &NaiveDateTime| if val.year() >= 2020 { Ok(()) } else { Err("too early") })
+            .validate_with(|val: &NaiveDateTime| if val.minute() == 0 { Ok(()) } else { Err("not on the hour") });
+        let validator = datetime_select.validator.as_ref().unwrap();
+        assert_eq!(validator(&NaiveDate::from_ymd(2020, 7, 8).and_hms(17, 0, 0)), None);
+        assert_eq!(validator(&NaiveDate::from_ymd(2019, 7, 8).and_hms(17, 30, 0)), Some("too early".to_string()));
+        assert_eq!(validator(&NaiveDate::from_ymd(2020, 7, 8).and_hms(17, 30, 0)), Some("not on the hour".to_string()));
+    }
+    #[test]
+    fn test_rejected_confirm() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select
+            .date_type(DateType::Date)
+            .default("2020-07-08T00:00:00Z")
+            .validate_with(|val: &NaiveDateTime| if val.day() != 8 { Ok(()) } else { Err("not on the 8th") })
+            .disabled_dates(vec![NaiveDate::from_ymd(2020, 7, 9)]);
+        // A validator and a disabled date reject Enter the same way.
+        let mut state = datetime_select.new_state(true, false);
+        assert_eq!(datetime_select.apply_key(&mut state, Key::Enter), KeyOutcome::Bell);
+        assert_eq!(state.feedback(), Some("not on the 8th"));
+        let mut state = datetime_select.new_state(true, false);
+        state.date_val = NaiveDate::from_ymd(2020, 7, 9).and_hms(0, 0, 0);
+        assert_eq!(datetime_select.apply_key(&mut state, Key::Enter), KeyOutcome::Bell);
+        assert_eq!(state.feedback(), Some("This date is not available"));
+    }
+    #[test]
+    fn test_on_change() {
+        let seen = Rc::new(std::cell::RefCell::new(Vec::new()));
+        let mut datetime_select = DateTimeSelect::new();
+        let sink = seen.clone();
+        datetime_select.default("2020-07-08T17:00:00Z").on_change(move |val: &NaiveDateTime| sink.borrow_mut().push(*val));
+        let mut state = datetime_select.state();
+        let mut notified = None;
+        datetime_select.notify_change(&mut notified, state.current());
+        assert_eq!(*seen.borrow(), vec![NaiveDate::from_ymd(2020, 7, 8).and_hms(17, 0, 0)]);
+        // Moving to another field leaves the value unchanged.
+        datetime_select.apply_key(&mut state, Key::ArrowRight);
+        datetime_select.notify_change(&mut notified, state.current());
+        assert_eq!(seen.borrow().len(), 1);
+        datetime_select.apply_key(&mut state, Key::ArrowUp);
+        datetime_select.notify_change(&mut notified, state.current());
+        assert_eq!(seen.borrow().last(), Some(&NaiveDate::from_ymd(2020, 8, 8).and_hms(17, 0, 0)));
+        assert_eq!(seen.borrow().len(), 2);
+    }
+    #[test]
+    fn test_relative_bounds() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.min_offset(Duration::zero()).max_offset(Duration::days(90));
+        let today = datetime_select.today();
+        assert_eq!(datetime_select.bounds(), (today, today + Duration::days(90)));
+        assert_eq!(datetime_select.check_date(today - Duration::days(1)), today);
+        assert_eq!(datetime_select.check_date(today + Duration::days(100)), today + Duration::days(90));
+    }
+    #[test]
+    fn test_theme_field_format() {
+        struct BracketTheme;
+        impl Theme for BracketTheme {
+            fn format_datetime_field(&self, f: &mut dyn fmt::Write, value: &str, active: bool) -> fmt::Result {
+                if active {
+                    write!(f, "[{}]", value)
+                } else {
+                    write!(f, "{}", value)
                 }
-                _ => {}
-            }
-            date_val = self.check_date(date_val);
-            render.clear()?;
-            if self.show_match {
-                term.clear_last_lines(1)?;
             }
         }
+        let datetime_select = DateTimeSelect::with_theme(&BracketTheme);
+        let date = NaiveDate::from_ymd(2024, 3, 7).and_hms(12, 5, 0);
+        assert_eq!(datetime_select.terminal_format(date, Zone::Utc, 1, &[], false), "2024-[03]-07 12:05:00");
     }
-}
+    #[test]
+    fn test_typed_year_out_of_bounds() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select
+            .date_type(DateType::Date)
+            .default("2020-06-15T00:00:00+00:00")
+            .min("2000-01-01T00:00:00+00:00")
+            .max("2030-12-31T00:00:00+00:00");
+        let state = apply_keys(&datetime_select, vec![Key::Char('9'), Key::Char('9'), Key::Char('9'), Key::Char('9')]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2030, 12, 31).and_hms(0, 0, 0));
+        assert!(state.digits.is_empty());
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let state = apply_keys(&datetime_select, vec![Key::Char('1'), Key::Char('9'), Key::Char('9'), Key::Char('9')]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2000, 1, 1).and_hms(0, 0, 0));
 
+        datetime_select.clamp_mode(ClampMode::SnapToField);
+        let state = apply_keys(&datetime_select, vec![Key::Char('9'), Key::Char('9'), Key::Char('9'), Key::Char('9')]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2030, 6, 15).and_hms(0, 0, 0));
+    }
     #[test]
-    fn test_defaults() {
+    fn test_field_range_builders() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.default("2020-06-15T08:30:00+00:00").hour_range(9, 17);
+        let state = datetime_select.new_state(false, false);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 6, 15).and_hms(9, 30, 0));
+
+        let hour = [Key::Tab, Key::Tab, Key::Tab];
+        let state = apply_keys(&datetime_select, [&hour[..], &[Key::ArrowDown]].concat());
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 6, 15).and_hms(9, 30, 0));
+        assert_eq!(state.feedback, Some("Already at the minimum".into()));
+        let state = apply_keys(&datetime_select, [&hour[..], &[Key::Char('2'), Key::Char('0')]].concat());
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 6, 15).and_hms(17, 30, 0));
+
+        // The stricter of the global bounds and the field range wins.
+        datetime_select.max("2020-06-15T12:00:00+00:00");
+        let state = apply_keys(&datetime_select, [&hour[..], &[Key::Char('1'), Key::Char('6')]].concat());
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 6, 15).and_hms(12, 0, 0));
+
+        match datetime_select.try_field_bounds(DateField::Minute, 30, 15) {
+            Err(DateTimeError::InvalidFieldBounds) => {}
+            _ => panic!("expected an invalid field bounds error"),
+        }
+        assert!(!datetime_select.field_bounds.contains_key(&DateField::Minute));
+    }
+    #[test]
+    fn test_read_only() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.default("2020-06-15T10:30:00+00:00").read_only(true);
+        let mut state = datetime_select.new_state(false, false);
+        for key in [Key::ArrowUp, Key::Tab, Key::Char('5'), Key::Char('t'), Key::Char(']')].iter().cloned() {
+            assert_eq!(datetime_select.apply_key(&mut state, key), KeyOutcome::Continue);
+        }
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 6, 15).and_hms(10, 30, 0));
+        assert_eq!(state.pos, 0);
+        assert_eq!(datetime_select.apply_key(&mut state, Key::Escape), KeyOutcome::Confirm);
+        assert_eq!(datetime_select.apply_key(&mut state, Key::Enter), KeyOutcome::Confirm);
+    }
+    #[test]
+    fn test_default_zone() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.timezone(Zone::Local).default_zone(DefaultZone::Utc);
+        assert_eq!(datetime_select.today(), Utc::today().naive_utc().and_hms(0, 0, 0));
+        datetime_select.default_zone(DefaultZone::Local);
+        assert_eq!(datetime_select.today(), Local::today().naive_local().and_hms(0, 0, 0));
+    }
+    #[test]
+    fn test_theme_frame_format() {
+        let theme = get_default_theme();
+        let (mut top, mut line, mut bottom) = (String::new(), String::new(), String::new());
+        theme.format_datetime_border(&mut top, 4, true).unwrap();
+        theme.format_datetime_framed(&mut line, "Date").unwrap();
+        theme.format_datetime_border(&mut bottom, 4, false).unwrap();
+        assert_eq!(console::strip_ansi_codes(&top), "┌──────┐");
+        assert_eq!(console::strip_ansi_codes(&line), "│ Date │");
+        assert_eq!(console::strip_ansi_codes(&bottom), "└──────┘");
+    }
+    #[test]
+    fn test_framed() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.date_type(DateType::Date).default("2024-03-07T00:00:00Z").with_prompt("Start").framed(true);
+        let mut state = datetime_select.state();
+        state.feedback = Some("too early".into());
+        let lines = |datetime_select: &DateTimeSelect, state: &mut DateTimeSelectState| -> (Vec<String>, usize) {
+            let (lines, value_lines) = datetime_select.frame_lines(state, false, 80);
+            (lines.iter().map(|line| console::strip_ansi_codes(line).into_owned()).collect(), value_lines)
+        };
+        // The error lines up with the datetime inside the box.
+        let (framed, value_lines) = lines(&datetime_select, &mut state);
+        assert_eq!(
+            framed,
+            vec![
+                "┌────────────────────────┐",
+                "│ Start: 2024-03-07, Thu │",
+                "└────────────────────────┘",
+                "         too early"
+            ]
+        );
+        assert_eq!(value_lines, 3);
+        // The caret is drawn inside the box rather than below it.
+        datetime_select.active_marker(Marker::Caret);
+        let (framed, value_lines) = lines(&datetime_select, &mut state);
+        assert_eq!(framed[2], "│        ^^^^            │");
+        assert_eq!(framed[3], "└────────────────────────┘");
+        assert_eq!(value_lines, 4);
+    }
+    #[test]
+    fn test_theme_error_format() {
+        let mut line = String::new();
+        get_default_theme().format_datetime_error(&mut line, &Some("Date".into()), "too early").unwrap();
+        assert_eq!(console::strip_ansi_codes(&line), "      too early");
+    }
+    #[test]
+    fn test_plain_field_format() {
         let datetime_select = DateTimeSelect::new();
-        assert_eq!(datetime_select.prompt, None);
-        assert_eq!(datetime_select.weekday, true);
-        assert_eq!(datetime_select.date_type, DateType::DateTime);
+        let date = NaiveDate::from_ymd(2024, 3, 7).and_hms(12, 5, 0);
+        assert_eq!(datetime_select.terminal_format(date, Zone::Utc, 0, &[], true), "[2024]-03-07 12:05:00");
     }
     #[test]
-    fn test_setting_proper_rfc3339_default() {
+    fn test_preview() {
         let mut datetime_select = DateTimeSelect::new();
-        datetime_select.default("2019-01-01T00:00:00-00:00");
-        assert_eq!(datetime_select.default, Some(NaiveDate::from_ymd(2019, 1, 1).and_hms_milli(0, 0, 0, 0)));
+        datetime_select.default("2024-03-07T12:05:00Z");
+        // The brackets depend on whether the tests run on a terminal.
+        let preview = datetime_select.preview();
+        let preview = console::strip_ansi_codes(&preview).replace(&['[', ']'][..], "");
+        assert_eq!(preview, "2024-03-07 12:05:00, Thu");
+        datetime_select.date_type(DateType::Time);
+        let preview = datetime_select.preview();
+        assert_eq!(console::strip_ansi_codes(&preview).replace(&['[', ']'][..], ""), "12:05:00");
     }
     #[test]
-    fn test_setting_prompt() {
+    fn test_no_weekday_for_time() {
         let mut datetime_select = DateTimeSelect::new();
-        datetime_select.with_prompt("test");
-        assert_eq!(datetime_select.prompt, Some("test".to_owned()));
+        datetime_select.default("2024-03-07T12:05:00Z").date_type(DateType::Time).weekday(true);
+        let date = NaiveDate::from_ymd(2024, 3, 7).and_hms(12, 5, 0);
+        for &position in [WeekdayPosition::Prefix, WeekdayPosition::Suffix].iter() {
+            datetime_select.weekday_position(position);
+            assert_eq!(datetime_select.format_value(date, Zone::Utc, usize::MAX, &[], true), "12:05:00");
+        }
+        datetime_select.date_type(DateType::Date);
+        assert_eq!(datetime_select.format_value(date, Zone::Utc, usize::MAX, &[], true), "2024-03-07, Thu");
     }
     #[test]
-    fn test_setting_weekday() {
+    fn test_weekday_position() {
         let mut datetime_select = DateTimeSelect::new();
-        datetime_select.weekday(false);
-        assert_eq!(datetime_select.weekday, false);
+        datetime_select
+            .default("2024-03-07T00:00:00Z")
+            .date_type(DateType::Date)
+            .with_prompt("Start")
+            .active_marker(Marker::Caret);
+        let mut state = datetime_select.state();
+        datetime_select.apply_key(&mut state, Key::ArrowRight);
+        let lines = |datetime_select: &DateTimeSelect, state: &mut DateTimeSelectState| -> Vec<String> {
+            let (lines, _) = datetime_select.frame_lines(state, false, 80);
+            lines.iter().map(|line| console::strip_ansi_codes(line).into_owned()).collect()
+        };
+        assert_eq!(lines(&datetime_select, &mut state), vec!["Start: 2024-03-07, Thu", "            ^^"]);
+        // The caret moves right by the weekday in front of the date.
+        datetime_select.weekday_position(WeekdayPosition::Prefix);
+        assert_eq!(lines(&datetime_select, &mut state), vec!["Start: Thu, 2024-03-07", "                 ^^"]);
     }
     #[test]
-    fn test_setting_valid_date_type() {
+    fn test_active_marker() {
+        let mut datetime_select = DateTimeSelect::new();
+        let date = NaiveDate::from_ymd(2024, 3, 7).and_hms(12, 5, 0);
+        datetime_select.active_marker(Marker::Brackets);
+        assert_eq!(console::strip_ansi_codes(&datetime_select.terminal_format(date, Zone::Utc, 1, &[], false)), "2024-[03]-07 12:05:00");
+        datetime_select.active_marker(Marker::Caret);
+        assert_eq!(datetime_select.terminal_format(date, Zone::Utc, 1, &[], true), "2024-03-07 12:05:00");
+        assert_eq!(datetime_select.active_span(date, Zone::Utc, 1, &[]), (5, 2));
+        assert_eq!(datetime_select.active_span(date, Zone::Utc, 3, &[]), (11, 2));
+        let early = NaiveDate::from_ymd(88, 3, 7).and_hms(12, 5, 0);
+        assert_eq!(datetime_select.terminal_format(early, Zone::Utc, 1, &[], true), "0088-03-07 12:05:00");
+        // Typed digits replace the active field, padded to the digits it needs.
+        assert_eq!(datetime_select.terminal_format(date, Zone::Utc, 0, &[2, 0], true), "20__-03-07 12:05:00");
+    }
+    #[test]
+    fn test_default_and_clone() {
+        let mut template: DateTimeSelect = Default::default();
+        template.date_type(DateType::Date).disable_if(|date| date.day() == 1);
+        let mut datetime_select = template.clone();
+        datetime_select.date_type(DateType::Time);
+        assert_eq!(template.date_type, DateType::Date);
+        assert_eq!(datetime_select.date_type, DateType::Time);
+        assert!(template.is_disabled(NaiveDate::from_ymd(2020, 7, 1).and_hms(0, 0, 0)));
+    }
+    fn apply_keys(datetime_select: &DateTimeSelect, keys: Vec<Key>) -> DateTimeSelectState {
+        let mut state = datetime_select.new_state(true, false);
+        for key in keys {
+            datetime_select.apply_key(&mut state, key);
+        }
+        state
+    }
+    #[test]
+    fn test_apply_key() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.default("2020-07-08T17:01:30Z").max("2030-01-01T00:00:00Z");
+
+        let keys = vec![Key::Char('2'), Key::Char('0'), Key::Char('2'), Key::Char('1'), Key::Tab, Key::Char('1'), Key::Char('2')];
+        let state = apply_keys(&datetime_select, keys);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2021, 12, 8).and_hms(17, 1, 30));
+        assert_eq!(state.pos, 1);
+
+        // Moving left from the first field wraps around to the last.
+        let state = apply_keys(&datetime_select, vec![Key::ArrowLeft, Key::ArrowUp]);
+        assert_eq!(state.pos, 5);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 7, 8).and_hms(17, 1, 31));
+
+        // A jump past max is clamped, an incomplete month is set when moving on.
+        let state = apply_keys(&datetime_select, vec![Key::Char('K')]);
+        assert_eq!(state.date_val, datetime_select.max);
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Char('1'), Key::ArrowRight]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 1, 8).and_hms(17, 1, 30));
+        assert!(state.digits.is_empty());
+
+        let mut state = datetime_select.new_state(true, false);
+        assert_eq!(datetime_select.apply_key(&mut state, Key::Enter), KeyOutcome::Confirm);
+        assert_eq!(state.current(), NaiveDate::from_ymd(2020, 7, 8).and_hms(17, 1, 30));
+        assert_eq!(datetime_select.apply_key(&mut state, Key::Escape), KeyOutcome::Cancel);
+    }
+    #[test]
+    fn test_early_digit_commit() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.default("2020-07-08T17:01:30Z");
+        // A minute of 9 can not take another digit, so it is set right away.
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Tab, Key::Tab, Key::Tab, Key::Char('9'), Key::Char('4')]);
+        assert_eq!(state.digits, vec![4]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 7, 8).and_hms(17, 9, 30));
+
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Char('2')]);
+        assert!(state.digits.is_empty());
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 2, 8).and_hms(17, 1, 30));
+
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Tab, Key::Tab, Key::Char('3')]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 7, 8).and_hms(3, 1, 30));
+        datetime_select.on_invalid_entry(InvalidEntry::Clamp);
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Tab, Key::Tab, Key::Char('2'), Key::Char('7')]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 7, 8).and_hms(23, 1, 30));
+    }
+    #[test]
+    fn test_day_clamped_to_month() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.default("2020-04-08T17:01:30Z");
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Tab, Key::Char('3'), Key::Char('1')]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 4, 30).and_hms(17, 1, 30));
+        assert_eq!(state.feedback, Some("This month only has 30 days".to_owned()));
+
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Tab, Key::Char('4')]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 4, 4).and_hms(17, 1, 30));
+        assert_eq!(state.feedback, None);
+    }
+    #[test]
+    fn test_step_at_bound() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.default("2020-07-08T17:01:30Z").max("2020-12-31T00:00:00Z");
+        let state = apply_keys(&datetime_select, vec![Key::ArrowUp]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 12, 31).and_hms(0, 0, 0));
+        assert_eq!(state.feedback, None);
+        let state = apply_keys(&datetime_select, vec![Key::ArrowUp, Key::ArrowUp]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 12, 31).and_hms(0, 0, 0));
+        assert_eq!(state.feedback, Some("Already at the maximum".to_owned()));
+
+        let mut state = datetime_select.state();
+        assert_eq!(datetime_select.apply_key(&mut state, Key::ArrowDown), KeyOutcome::Continue);
+        assert_eq!(state.feedback, None);
+    }
+    #[test]
+    fn test_today_key() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.default("2000-07-08T17:01:30Z").date_type(DateType::Date);
+        let state = apply_keys(&datetime_select, vec![Key::ArrowUp, Key::Char('t')]);
+        assert_eq!(state.date_val, datetime_select.today());
+
+        datetime_select.today_key(Key::Home).max("2010-01-01T00:00:00Z");
+        let state = apply_keys(&datetime_select, vec![Key::Home]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2010, 1, 1).and_hms(0, 0, 0));
+    }
+    #[test]
+    fn test_report_stream() {
+        let mut datetime_select = DateTimeSelect::new();
+        assert_eq!(datetime_select.term().target(), TermTarget::Stderr);
+        datetime_select.report_stream(TermTarget::Stdout);
+        assert_eq!(datetime_select.term().target(), TermTarget::Stdout);
+    }
+    #[test]
+    fn test_confirm_key() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.confirm_key(Key::Char(' '));
+        let mut state = datetime_select.state();
+        assert_eq!(datetime_select.apply_key(&mut state, Key::Enter), KeyOutcome::Continue);
+        assert_eq!(datetime_select.apply_key(&mut state, Key::Char(' ')), KeyOutcome::Confirm);
+    }
+    #[test]
+    fn test_partial_digits() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.default("2024-03-07T12:05:00Z");
+        // A month of 2 can not take another digit, a day of 3 can.
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Char('2')]);
+        assert_eq!(state.date_val.month(), 2);
+        assert_eq!(state.pos, 1);
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Tab, Key::Char('3')]);
+        assert_eq!(state.date_val.day(), 7);
+        assert_eq!(state.digits, vec![3]);
+
+        // Leaving the field sets what was typed so far.
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Tab, Key::Tab, Key::Char('7'), Key::Tab]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2024, 3, 7).and_hms(7, 5, 0));
+        assert_eq!(state.pos, 4);
+        let mut state = datetime_select.new_state(false, false);
+        for key in [Key::Tab, Key::Tab, Key::Char('3')].iter().cloned() {
+            datetime_select.apply_key(&mut state, key);
+        }
+        assert_eq!(datetime_select.apply_key(&mut state, Key::Enter), KeyOutcome::Confirm);
+        assert_eq!(state.date_val.day(), 3);
+    }
+    #[test]
+    fn test_number_select() {
+        let mut number_select = NumberSelect::new();
+        number_select.min(5).max(45).default(10);
+        let keys = |number_select: &NumberSelect, keys: Vec<Key>| {
+            let (mut num, mut digits) = (10, Vec::new());
+            let outcomes: Vec<KeyOutcome> =
+                keys.into_iter().map(|key| number_select.apply_key(&mut num, &mut digits, key, true)).collect();
+            (num, digits, outcomes)
+        };
+        assert_eq!(keys(&number_select, vec![Key::Char('1')]).1, vec![1]);
+        assert_eq!(keys(&number_select, vec![Key::Char('1'), Key::Char('2')]).0, 12);
+        // A first 5 can only become 50 or more, past max.
+        assert_eq!(keys(&number_select, vec![Key::Char('5')]).0, 5);
+        assert_eq!(keys(&number_select, vec![Key::Char('0'), Key::Char('2')]).0, 5);
+        assert_eq!(keys(&number_select, vec![Key::Char('3'), Key::Enter]).0, 5);
+        assert_eq!(number_select.check_number(-3), 5);
+        assert_eq!(number_select.check_number(60), 45);
+        // Stepping lands on max, only the next step is refused.
+        number_select.step(30);
+        let (num, _, outcomes) = keys(&number_select, vec![Key::ArrowUp, Key::ArrowUp, Key::ArrowUp, Key::Escape]);
+        assert_eq!(num, 45);
+        assert_eq!(outcomes, vec![KeyOutcome::Continue, KeyOutcome::Continue, KeyOutcome::Bell, KeyOutcome::Cancel]);
+        number_select.max(120);
+        assert_eq!(keys(&number_select, vec![Key::Char('9'), Key::Char('9')]).0, 99);
+        assert_eq!(keys(&number_select, vec![Key::Char('1'), Key::Char('2')]).1, vec![1, 2]);
+        assert_eq!(number_select.terminal_format(7, &[1], true), "[1_]");
+
+        match number_select.try_step(0) {
+            Err(DateTimeError::ZeroStep) => {}
+            _ => panic!("expected a zero step error"),
+        }
+        assert_eq!(number_select.step, 30);
+
+        number_select.min(130);
+        assert_eq!(number_select.interact_on(&Term::stderr()).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+    #[test]
+    fn test_step_at_bounds() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.date_type(DateType::Date).default("2024-03-16T00:00:00Z").min("2024-03-15").max("2024-03-17");
+        let mut state = datetime_select.new_state(false, false);
+        state.pos = 2;
+        assert_eq!(datetime_select.apply_key(&mut state, Key::ArrowDown), KeyOutcome::Continue);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2024, 3, 15).and_hms(0, 0, 0));
+        assert_eq!(datetime_select.apply_key(&mut state, Key::ArrowDown), KeyOutcome::Bell);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2024, 3, 15).and_hms(0, 0, 0));
+        assert_eq!(state.feedback, Some("Already at the minimum".into()));
+
+        // Stepping the month past max lands on max in one press, only the next press is refused.
+        state.pos = 1;
+        state.feedback = None;
+        assert_eq!(datetime_select.apply_key(&mut state, Key::ArrowUp), KeyOutcome::Continue);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2024, 3, 17).and_hms(0, 0, 0));
+        assert_eq!(state.feedback, None);
+        assert_eq!(datetime_select.apply_key(&mut state, Key::ArrowUp), KeyOutcome::Bell);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2024, 3, 17).and_hms(0, 0, 0));
+        assert_eq!(state.feedback, Some("Already at the maximum".into()));
+    }
+    #[test]
+    fn test_allow_empty() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.date_type(DateType::Date).default("2024-03-07T00:00:00Z").allow_empty(true);
+        let mut state = datetime_select.state();
+        assert!(state.is_empty());
+        assert_eq!(datetime_select.empty_format(state.date_val, Zone::Utc, usize::MAX, true), "----------");
+        datetime_select.apply_key(&mut state, Key::Tab);
+        assert!(state.is_empty());
+        assert_eq!(datetime_select.apply_key(&mut state, Key::Enter), KeyOutcome::Confirm);
+        assert!(state.selected().empty);
+
+        datetime_select.apply_key(&mut state, Key::ArrowUp);
+        assert!(!state.is_empty());
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2024, 4, 7).and_hms(0, 0, 0));
+        let state = apply_keys(&datetime_select, vec![Key::Char('2')]);
+        assert!(!state.is_empty());
+    }
+    #[test]
+    fn test_advance_key() {
         let mut datetime_select = DateTimeSelect::new();
         datetime_select.date_type(DateType::Date);
-        assert_eq!(datetime_select.date_type, DateType::Date);
+        let state = apply_keys(&datetime_select, vec![Key::Char(' '), Key::Char(' ')]);
+        assert_eq!(state.pos, 2);
+        let state = apply_keys(&datetime_select, vec![Key::Char(' '), Key::Char(' '), Key::Char(' ')]);
+        assert_eq!(state.pos, 0);
+        let state = apply_keys(&datetime_select, vec![Key::Char('2'), Key::Char('1'), Key::Char(' ')]);
+        assert_eq!(state.date_val.year(), 2021);
+        assert_eq!(state.pos, 1);
+
+        datetime_select.advance_key(Key::Char('n'));
+        let state = apply_keys(&datetime_select, vec![Key::Char(' '), Key::Char('n')]);
+        assert_eq!(state.pos, 1);
     }
     #[test]
-    fn test_max_min_datetimes() {
+    fn test_default_from_env() {
+        env::set_var("DIALOGUER_TEST_DEFAULT", "2020-07-08T17:01:30Z");
+        env::set_var("DIALOGUER_TEST_BAD_DEFAULT", "tomorrow");
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.default_from_env("DIALOGUER_TEST_DEFAULT");
+        assert_eq!(datetime_select.default, Some(NaiveDate::from_ymd(2020, 7, 8).and_hms(17, 1, 30)));
+        datetime_select.default_from_env("DIALOGUER_TEST_BAD_DEFAULT").default_from_env("DIALOGUER_TEST_UNSET");
+        assert_eq!(datetime_select.default, Some(NaiveDate::from_ymd(2020, 7, 8).and_hms(17, 1, 30)));
+    }
+    #[test]
+    fn test_allow_typing() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.default("2020-07-08T17:01:30Z").allow_typing(false);
+        let state = apply_keys(&datetime_select, vec![Key::Char('1'), Key::Char('9'), Key::Char('9'), Key::Char('9'), Key::ArrowUp]);
+        assert!(state.digits.is_empty());
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2021, 7, 8).and_hms(17, 1, 30));
+    }
+    #[test]
+    fn test_leading_zero() {
         let mut datetime_select = DateTimeSelect::new();
+        datetime_select.default("2020-07-08T17:01:30Z");
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Char('0'), Key::Char('3')]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 3, 8).and_hms(17, 1, 30));
 
-        datetime_select.min("2020-02-20T02:20:25Z");
-        let min_date = NaiveDate::from_ymd(2020, 2, 20).and_hms(2, 20, 25);
-        assert_eq!(datetime_select.min, min_date);
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Char('0'), Key::Char('0'), Key::Char('3')]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 3, 8).and_hms(17, 1, 30));
 
-        datetime_select.max("2022-11-30T00:00:00Z");
-        let max_date = NaiveDate::from_ymd(2022, 11, 30).and_hms(0, 0, 0);
-        assert_eq!(datetime_select.max, max_date);
+        let state = apply_keys(&datetime_select, vec![Key::Tab, Key::Tab, Key::Char('0'), Key::Char('0'), Key::Char('5')]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 7, 5).and_hms(17, 1, 30));
+    }
+    #[test]
+    fn test_truncated_default() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.default_datetime(NaiveDate::from_ymd(2020, 7, 8).and_hms_milli(17, 1, 30, 250));
+        assert_eq!(datetime_select.initial_value(), NaiveDate::from_ymd(2020, 7, 8).and_hms(17, 1, 30));
 
-        let in_range_date = NaiveDate::from_ymd(2020, 7, 8).and_hms(17, 1, 30);
-        assert_eq!(datetime_select.check_date(in_range_date), in_range_date);
+        datetime_select.date_type(DateType::Date);
+        assert_eq!(datetime_select.initial_value(), NaiveDate::from_ymd(2020, 7, 8).and_hms(0, 0, 0));
 
-        assert_eq!(datetime_select.check_date(NaiveDate::from_ymd(2000, 1, 1).and_hms(0, 0, 0)), min_date);
-        assert_eq!(datetime_select.check_date(NaiveDate::from_ymd(2030, 1, 1).and_hms(0, 0, 0)), max_date);
+        datetime_select.date_type(DateType::Time).subsecond(Precision::Millis);
+        assert_eq!(datetime_select.initial_value(), NaiveDate::from_ymd(2020, 7, 8).and_hms_milli(17, 1, 30, 250));
+        datetime_select.show_seconds(false);
+        assert_eq!(datetime_select.initial_value(), NaiveDate::from_ymd(2020, 7, 8).and_hms(17, 1, 0));
+    }
+    #[test]
+    fn test_time_bounds() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.date_type(DateType::Time).min("2020-07-08T09:00:00Z").max("2020-07-08T17:00:00Z");
+        // A stale date does not move the time to a bound.
+        let date = NaiveDate::from_ymd(2021, 1, 1).and_hms(12, 0, 0);
+        assert_eq!(datetime_select.check_date(date), date);
+        let date = NaiveDate::from_ymd(2020, 1, 1).and_hms(18, 0, 0);
+        assert_eq!(datetime_select.check_date(date), NaiveDate::from_ymd(2020, 1, 1).and_hms(17, 0, 0));
+
+        // Bounds straddling midnight allow the late evening and early morning.
+        datetime_select.max("2020-07-09T06:00:00Z").min("2020-07-08T22:00:00Z");
+        let late = NaiveDate::from_ymd(2020, 1, 1).and_hms(23, 30, 0);
+        let early = NaiveDate::from_ymd(2020, 1, 1).and_hms(5, 0, 0);
+        assert_eq!(datetime_select.check_date(late), late);
+        assert_eq!(datetime_select.check_date(early), early);
+        let morning = NaiveDate::from_ymd(2020, 1, 1).and_hms(8, 0, 0);
+        assert_eq!(datetime_select.check_date(morning), NaiveDate::from_ymd(2020, 1, 1).and_hms(6, 0, 0));
+        let evening = NaiveDate::from_ymd(2020, 1, 1).and_hms(20, 0, 0);
+        assert_eq!(datetime_select.check_date(evening), NaiveDate::from_ymd(2020, 1, 1).and_hms(22, 0, 0));
+
+        // The same window with the max before the min is only allowed for Time.
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.date_type(DateType::Time).min("2020-07-08T22:00:00Z").max("2020-07-08T06:00:00Z");
+        assert_eq!(datetime_select.check_date(late), late);
+        assert_eq!(datetime_select.check_date(morning), NaiveDate::from_ymd(2020, 1, 1).and_hms(6, 0, 0));
+        assert!(datetime_select.check_range().is_ok());
+        datetime_select.date_type(DateType::DateTime);
+        assert!(datetime_select.check_range().is_err());
+        // The builder order does not matter, the type can come after the bounds.
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.min("2020-07-08T22:00:00Z").max("2020-07-08T06:00:00Z").date_type(DateType::Time);
+        assert!(datetime_select.check_range().is_ok());
+        assert_eq!(datetime_select.check_date(late), late);
+
+        datetime_select.date_type(DateType::Date);
+        let date = NaiveDate::from_ymd(2020, 7, 8).and_hms(0, 0, 0);
+        assert_eq!(datetime_select.check_date(date), date);
+    }
+    #[test]
+    fn test_snap_to_bounds() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.default("2020-07-08T17:01:30Z").min("2020-07-01T09:00:00Z").max("2020-07-31T17:00:00Z");
+        let state = apply_keys(&datetime_select, vec![Key::Char('[')]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 7, 1).and_hms(9, 0, 0));
+        let state = apply_keys(&datetime_select, vec![Key::Char(']')]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 7, 31).and_hms(17, 0, 0));
+    }
+    #[test]
+    fn test_acceleration() {
+        let mut acceleration = Acceleration::default();
+        let start = Instant::now();
+        let at = |millis| start + StdDuration::from_millis(millis);
+        for step in 0..ACCELERATE_AFTER as u64 {
+            assert_eq!(acceleration.repeats(&Key::ArrowUp, at(step * 30)), 1);
+        }
+        assert_eq!(acceleration.repeats(&Key::ArrowUp, at(300)), ACCELERATED_STEPS);
+        // Changing direction or pausing starts over.
+        assert_eq!(acceleration.repeats(&Key::ArrowDown, at(330)), 1);
+        assert_eq!(acceleration.repeats(&Key::ArrowDown, at(1000)), 1);
+        assert_eq!(acceleration.repeats(&Key::Char('1'), at(1010)), 1);
+    }
+    #[test]
+    fn test_parts() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.date_type(DateType::Time).show_seconds(false);
+        let parts = datetime_select.parts(NaiveDate::from_ymd(2020, 7, 8).and_hms(17, 1, 0));
+        assert_eq!((parts.year, parts.month, parts.day), (2020, 7, 8));
+        assert_eq!((parts.hour, parts.minute, parts.second), (17, 1, 0));
+        assert!(!parts.seconds_shown);
+        assert_eq!(parts.date_type, DateType::Time);
+    }
+    #[test]
+    fn test_require_interaction() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.require_interaction(true);
+        let mut state = datetime_select.state();
+        assert_eq!(datetime_select.apply_key(&mut state, Key::Enter), KeyOutcome::Bell);
+        assert_eq!(state.feedback, Some("Please make a selection first".to_owned()));
+        assert_eq!(datetime_select.apply_key(&mut state, Key::Char('?')), KeyOutcome::Continue);
+        assert_eq!(datetime_select.apply_key(&mut state, Key::Enter), KeyOutcome::Bell);
+        assert_eq!(datetime_select.apply_key(&mut state, Key::ArrowRight), KeyOutcome::Continue);
+        assert_eq!(datetime_select.apply_key(&mut state, Key::Enter), KeyOutcome::Confirm);
+    }
+    #[test]
+    fn test_backspace() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.default("2020-07-08T17:01:30Z");
+        let state = apply_keys(&datetime_select, vec![Key::Char('2'), Key::Char('1'), Key::Backspace]);
+        assert_eq!(state.digits, vec![2]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2020, 7, 8).and_hms(17, 1, 30));
+
+        let state = apply_keys(&datetime_select, vec![Key::Backspace]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2019, 7, 8).and_hms(17, 1, 30));
+    }
+    #[test]
+    fn test_fit_prompt() {
+        assert_eq!(fit_prompt("Pick a date", 2, 20, 80), "Pick a date");
+        assert_eq!(fit_prompt("Pick the date of your next appointment", 2, 20, 32), "Pick the…");
+        assert_eq!(fit_prompt("Pick a date", 2, 30, 32), "Pick a date");
+    }
+    #[test]
+    fn test_wrapped_rows() {
+        assert_eq!(wrapped_rows(0, 80), 1);
+        assert_eq!(wrapped_rows(80, 80), 1);
+        assert_eq!(wrapped_rows(81, 80), 2);
+        assert_eq!(wrapped_rows(30, 0), 1);
+    }
+    #[test]
+    fn test_short_years() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.default("2020-07-08T17:01:30Z");
+        let state = apply_keys(&datetime_select, vec![Key::Char('8'), Key::Char('8'), Key::Tab]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(1988, 7, 8).and_hms(17, 1, 30));
+        let state = apply_keys(&datetime_select, vec![Key::Char('5'), Key::ArrowRight]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(2005, 7, 8).and_hms(17, 1, 30));
+        let state = apply_keys(&datetime_select, vec![Key::Char('9'), Key::Char('9'), Key::Char('9'), Key::Tab]);
+        assert_eq!(state.date_val, NaiveDate::from_ymd(999, 7, 8).and_hms(17, 1, 30));
+
+        datetime_select.two_digit_year_pivot(90);
+        let mut state = datetime_select.new_state(true, false);
+        datetime_select.apply_key(&mut state, Key::Char('8'));
+        datetime_select.apply_key(&mut state, Key::Char('8'));
+        assert_eq!(datetime_select.apply_key(&mut state, Key::Enter), KeyOutcome::Confirm);
+        assert_eq!(state.current().year(), 2088);
+    }
+    #[test]
+    fn test_external_state() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.default("2020-07-08T17:01:30Z").date_type(DateType::Date);
+        let mut state = datetime_select.state();
+        assert_eq!(state.fields(), &[DateField::Year, DateField::Month, DateField::Day][..]);
+        assert_eq!(datetime_select.apply_key(&mut state, Key::ArrowRight), KeyOutcome::Continue);
+        assert_eq!(datetime_select.apply_key(&mut state, Key::ArrowUp), KeyOutcome::Continue);
+        assert_eq!(state.position(), 1);
+        assert_eq!(state.current(), NaiveDate::from_ymd(2020, 8, 8).and_hms(0, 0, 0));
+    }
+    #[test]
+    fn test_prompt_suffix() {
+        let mut datetime_select = DateTimeSelect::new();
+        assert_eq!(datetime_select.prompt_text(), None);
+        datetime_select.prompt_suffix("(YYYY-MM-DD)");
+        assert_eq!(datetime_select.prompt_text(), Some("(YYYY-MM-DD)".to_string()));
+        datetime_select.with_prompt("Start");
+        assert_eq!(datetime_select.prompt_text(), Some("Start (YYYY-MM-DD)".to_string()));
+    }
+    #[test]
+    fn test_prompt_on_own_line() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.date_type(DateType::Date).default("2024-03-07T00:00:00Z").with_prompt("Start");
+        let mut state = datetime_select.state();
+        assert_eq!(datetime_select.frame_lines(&mut state, true, 80), (vec!["Start: [2024]-03-07, Thu".to_string()], 1));
+        datetime_select.prompt_on_own_line(true);
+        let (lines, value_lines) = datetime_select.frame_lines(&mut state, true, 80);
+        assert_eq!(lines, vec!["Start:", "[2024]-03-07, Thu"]);
+        assert_eq!(value_lines, 2);
+    }
+    #[test]
+    fn test_confirmation_line() {
+        let mut datetime_select = DateTimeSelect::new();
+        datetime_select.date_type(DateType::Date).default("2024-03-07T00:00:00Z").clear(false);
+        let state = datetime_select.state();
+        assert_eq!(datetime_select.confirmation_line(&state.selected(), false), "2024-03-07");
+        datetime_select.with_prompt("Start");
+        assert_eq!(datetime_select.confirmation_line(&state.selected(), false), "Start: 2024-03-07");
+        // A range leaves both endpoints behind.
+        let mut state = datetime_select.new_state(false, true);
+        datetime_select.apply_key(&mut state, Key::Tab);
+        datetime_select.apply_key(&mut state, Key::ArrowUp);
+        assert_eq!(datetime_select.confirmation_line(&state.selected(), true), "Start: 2024-03-07 – 2025-03-07");
+    }
+    #[test]
+    fn test_prompt_separator() {
+        let mut datetime_select = DateTimeSelect::new();
+        let prompt = Some("Start?".to_string());
+        assert_eq!(datetime_select.datetime_line(&prompt, "2024-03-07"), "Start?: 2024-03-07");
+        assert_eq!(console::strip_ansi_codes(&datetime_select.error_line(&prompt, "too early")), "        too early");
+        datetime_select.prompt_separator(false);
+        assert_eq!(datetime_select.datetime_line(&prompt, "2024-03-07"), "Start? 2024-03-07");
+        assert_eq!(console::strip_ansi_codes(&datetime_select.error_line(&prompt, "too early")), "       too early");
+        assert_eq!(datetime_select.datetime_line(&None, "2024-03-07"), "2024-03-07");
     }
 }
@@ -19,9 +19,15 @@
 //! * Menu fuzzy match selections
 //! * DateTime selections
 extern crate chrono;
+#[cfg(feature = "chrono-tz")]
+extern crate chrono_tz;
 extern crate console;
 extern crate tempfile;
-pub use datetime::{DateTimeSelect, DateType};
+pub use datetime::{
+    ClampMode, DateField, DateTimeError, DateTimeParts, DateTimeSelect, DateTimeSelectState, DateType, DefaultZone, DurationSelect,
+    InvalidEntry, KeyOutcome, Marker, NumberSelect, Precision, QuarterSelect, WeekdayFormat, WeekdayPosition, Zone, days_in_month,
+    is_valid_rfc3339, parse_bound,
+};
 pub use edit::Editor;
 pub use prompts::{Confirmation, Input, PasswordInput};
 pub use select::{Checkboxes, FuzzySelect, OrderList, Select};
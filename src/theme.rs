@@ -2,7 +2,7 @@
 use std::fmt;
 use std::io;
 
-use console::{Style, Term};
+use console::{measure_text_width, style, Style, Term};
 
 /// Rendering style for a selected item
 #[derive(Debug, Clone, Copy)]
@@ -141,6 +141,43 @@ pub trait Theme {
             None => write!(f, "{}", datetime),
         }
     }
+
+    /// Formats an error shown below a datetime selection, lined up with the datetime after the prompt.
+    fn format_datetime_error(&self, f: &mut dyn fmt::Write, prompt: &Option<String>, err: &str) -> fmt::Result {
+        let indent = prompt.as_ref().map_or(0, |prompt| measure_text_width(prompt) + 2);
+        write!(f, "{:indent$}{}", "", style(err).red(), indent = indent)
+    }
+
+    /// Formats a single field of a datetime selection, the active field is the one being edited.
+    fn format_datetime_field(&self, f: &mut dyn fmt::Write, value: &str, active: bool) -> fmt::Result {
+        if active {
+            write!(f, "{}", style(value).bold())
+        } else {
+            write!(f, "{}", style(value).dim())
+        }
+    }
+
+    /// Formats a single day of a datetime calendar grid.
+    fn format_calendar_day(&self, f: &mut dyn fmt::Write, day: u32, selected: bool) -> fmt::Result {
+        if selected {
+            write!(f, "[{:>2}]", day)
+        } else {
+            write!(f, " {:>2} ", day)
+        }
+    }
+
+    /// Formats the top or bottom border of a framed datetime selection around `width` columns.
+    fn format_datetime_border(&self, f: &mut dyn fmt::Write, width: usize, top: bool) -> fmt::Result {
+        let (left, right) = if top { ("┌", "┐") } else { ("└", "┘") };
+        write!(f, "{}", style(format!("{}{}{}", left, "─".repeat(width + 2), right)).dim())
+    }
+
+    /// Formats the datetime line of a framed datetime selection, between the borders.
+    ///
+    /// Adds two columns on each side, as the borders expect.
+    fn format_datetime_framed(&self, f: &mut dyn fmt::Write, line: &str) -> fmt::Result {
+        write!(f, "{} {} {}", style("│").dim(), line, style("│").dim())
+    }
 }
 
 /// The default theme.
@@ -403,6 +440,25 @@ impl Theme for ColorfulTheme {
             SelectionStyle::MenuUnselected => write!(f, "  {}", self.inactive_style.apply_to(text)),
         }
     }
+
+    fn format_datetime_error(&self, f: &mut dyn fmt::Write, prompt: &Option<String>, err: &str) -> fmt::Result {
+        let indent = prompt.as_ref().map_or(0, |prompt| measure_text_width(prompt) + 2);
+        write!(f, "{:indent$}{}", "", self.error_style.apply_to(err), indent = indent)
+    }
+
+    fn format_calendar_day(&self, f: &mut dyn fmt::Write, day: u32, selected: bool) -> fmt::Result {
+        if selected {
+            write!(
+                f,
+                "{}{}{}",
+                self.indicator_style.apply_to("["),
+                self.active_style.apply_to(format!("{:>2}", day)),
+                self.indicator_style.apply_to("]")
+            )
+        } else {
+            write!(f, " {} ", self.inactive_style.apply_to(format!("{:>2}", day)))
+        }
+    }
 }
 
 /// Helper struct to conveniently render a theme ot a term.
@@ -532,10 +588,6 @@ impl<'a> TermThemeRenderer<'a> {
         self.write_formatted_line(|this, buf| this.theme.format_selection(buf, text, style))
     }
 
-    pub fn datetime(&mut self, text: &Option<String>, datetime: &str) -> io::Result<()> {
-        self.write_formatted_line(|this, buf| this.theme.format_datetime(buf, text, datetime))
-    }
-
     pub fn clear(&mut self) -> io::Result<()> {
         self.term
             .clear_last_lines(self.height + self.prompt_height)?;